@@ -12,7 +12,7 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{Hash, HashEngine};
+use crate::{sha256, Hash, HashEngine};
 
 /// A hash computed from a RFC 2104 HMAC. Parameterized by the underlying hash function.
 #[derive(Copy, Clone, PartialOrd, Ord, Hash)]
@@ -87,6 +87,51 @@ pub fn from_inner_engines(iengine: T, oengine: T) -> HmacEngine<T> {
     }
 }
 
+impl HmacEngine<sha256::HashEngine> {
+    /// Constructs a new HMAC-SHA256 engine from the inner and outer midstates of a key.
+    ///
+    /// This skips processing the key, see [`HmacMidstate`].
+    pub fn from_midstates(inner: sha256::Midstate, outer: sha256::Midstate) -> Self {
+        HmacEngine {
+            iengine: sha256::HashEngine::from_midstate(inner),
+            oengine: sha256::HashEngine::from_midstate(outer),
+        }
+    }
+}
+
+/// The inner and outer midstates of an HMAC-SHA256 key.
+///
+/// Deriving these costs two block compressions per key, when authenticating many messages with
+/// the same key compute them once and construct each engine with [`Self::engine`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct HmacMidstate {
+    inner: sha256::Midstate,
+    outer: sha256::Midstate,
+}
+
+impl HmacMidstate {
+    /// Derives the inner and outer midstates of `key`.
+    pub fn new(key: &[u8]) -> Self {
+        let engine = HmacEngine::<sha256::HashEngine>::new(key);
+        // The engines have each hashed exactly one padded key block.
+        HmacMidstate {
+            inner: engine.iengine.midstate().expect("exactly one block hashed"),
+            outer: engine.oengine.midstate().expect("exactly one block hashed"),
+        }
+    }
+
+    /// Returns the midstate of the inner hash engine.
+    pub fn inner(&self) -> sha256::Midstate { self.inner }
+
+    /// Returns the midstate of the outer hash engine.
+    pub fn outer(&self) -> sha256::Midstate { self.outer }
+
+    /// Constructs a new HMAC-SHA256 engine keyed with these midstates.
+    pub fn engine(&self) -> HmacEngine<sha256::HashEngine> {
+        HmacEngine::from_midstates(self.inner, self.outer)
+    }
+}
+
 impl<T: HashEngine> HashEngine for HmacEngine<T> {
     type Hash = Hmac<T::Hash>;
     type Bytes = T::Bytes;
@@ -286,6 +331,24 @@ struct Test {
         }
     }
 
+    #[test]
+    fn hmac_midstate() {
+        use crate::hmac::HmacMidstate;
+        use crate::{sha256, HashEngine, HmacEngine};
+
+        for key in [&[][..], &[0x0b; 20], &[0xaa; 64], &[0xaa; 131]] {
+            let midstate = HmacMidstate::new(key);
+            assert_eq!(midstate.inner().as_parts().1, 64);
+            assert_eq!(midstate.outer().as_parts().1, 64);
+
+            let mut engine = HmacEngine::<sha256::HashEngine>::new(key);
+            engine.input(b"Hi There");
+            let mut cached = midstate.engine();
+            cached.input(b"Hi There");
+            assert_eq!(cached.finalize(), engine.finalize());
+        }
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn hmac_sha512_serde() {
@@ -321,8 +384,33 @@ fn hmac_sha512_serde() {
 mod benches {
     use test::Bencher;
 
+    use crate::hmac::HmacMidstate;
     use crate::{sha256, HashEngine as _, HmacEngine};
 
+    #[bench]
+    pub fn hmac_sha256_64_fresh_key(bh: &mut Bencher) {
+        let key = [0xaa; 32];
+        let bytes = [1u8; 64];
+        bh.iter(|| {
+            let mut engine = HmacEngine::<sha256::HashEngine>::new(&key);
+            engine.input(&bytes);
+            engine.finalize()
+        });
+        bh.bytes = bytes.len() as u64;
+    }
+
+    #[bench]
+    pub fn hmac_sha256_64_cached_key(bh: &mut Bencher) {
+        let midstate = HmacMidstate::new(&[0xaa; 32]);
+        let bytes = [1u8; 64];
+        bh.iter(|| {
+            let mut engine = midstate.engine();
+            engine.input(&bytes);
+            engine.finalize()
+        });
+        bh.bytes = bytes.len() as u64;
+    }
+
     #[bench]
     pub fn hmac_sha256_10(bh: &mut Bencher) {
         let mut engine = HmacEngine::<sha256::HashEngine>::new(&[]);
@@ -108,6 +108,7 @@ pub mod _core {
 pub mod macros;
 pub mod ripemd160;
 pub mod sha1;
+pub mod sha224;
 pub mod sha256;
 pub mod sha256d;
 pub mod sha256t;
@@ -145,6 +146,9 @@ pub mod serde_details {
 /// SHA-1: Alias for the [`sha1::Hash`] hash type.
 #[doc(inline)]
 pub use sha1::Hash as Sha1;
+/// SHA-224: Alias for the [`sha224::Hash`] hash type.
+#[doc(inline)]
+pub use sha224::Hash as Sha224;
 /// SHA-256: Alias for the [`sha256::Hash`] hash type.
 #[doc(inline)]
 pub use sha256::Hash as Sha256;
@@ -254,7 +258,10 @@ pub trait IsByteArray {}
     impl<const N: usize> IsByteArray for [u8; N] {}
 }
 
-fn incomplete_block_len<H: HashEngine>(eng: &H) -> usize {
+/// Returns the number of bytes input into the current, not yet complete, block of `eng`.
+///
+/// This is `eng.n_bytes_hashed() % H::BLOCK_SIZE`, useful when implementing engine wrappers.
+pub fn incomplete_block_len<H: HashEngine>(eng: &H) -> usize {
     let block_size = <H as HashEngine>::BLOCK_SIZE as u64; // Cast usize to u64 is ok.
 
     // After modulo operation we know cast u64 to usize as ok.
@@ -314,4 +321,14 @@ fn newtype_fmt_roundtrip() {
         let rinsed = hex.parse::<TestNewtype>().expect("failed to parse hex");
         assert_eq!(rinsed, orig)
     }
+
+    #[test]
+    fn incomplete_block_len() {
+        let mut engine = sha256d::Hash::engine();
+        assert_eq!(super::incomplete_block_len(&engine), 0);
+        engine.input(&[0; 100]);
+        assert_eq!(super::incomplete_block_len(&engine), 36);
+        engine.input(&[0; 28]);
+        assert_eq!(super::incomplete_block_len(&engine), 0);
+    }
 }
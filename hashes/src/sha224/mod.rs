@@ -0,0 +1,168 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! SHA224 implementation.
+
+use crate::sha256;
+
+crate::internal_macros::general_hash_type! {
+    224,
+    false,
+    "Output of the SHA224 hash function."
+}
+
+fn from_engine(e: HashEngine) -> Hash {
+    let mut ret = [0; 28];
+    ret.copy_from_slice(&sha256::from_engine(e.0).as_byte_array()[..28]);
+    Hash(ret)
+}
+
+/// Engine to compute SHA224 hash function.
+#[derive(Debug, Clone)]
+pub struct HashEngine(sha256::HashEngine);
+
+impl HashEngine {
+    /// Constructs a new SHA224 hash engine.
+    pub const fn new() -> Self { Self(sha256::HashEngine::sha224()) }
+}
+
+impl Default for HashEngine {
+    fn default() -> Self { Self::new() }
+}
+
+impl crate::HashEngine for HashEngine {
+    type Hash = Hash;
+    type Bytes = [u8; 28];
+    const BLOCK_SIZE: usize = sha256::BLOCK_SIZE;
+
+    fn n_bytes_hashed(&self) -> u64 { self.0.n_bytes_hashed() }
+    fn input(&mut self, inp: &[u8]) { self.0.input(inp); }
+    fn finalize(self) -> Self::Hash { Hash::from_engine(self) }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[cfg(feature = "hex")]
+    fn test() {
+        use alloc::string::ToString;
+
+        use crate::{sha224, HashEngine};
+
+        #[derive(Clone)]
+        struct Test {
+            input: &'static str,
+            output: [u8; 28],
+            output_str: &'static str,
+        }
+
+        #[rustfmt::skip]
+        let tests = [
+            // Examples from go sha256 (sha224) tests.
+            Test {
+                input: "",
+                output: [
+                    0xd1, 0x4a, 0x02, 0x8c, 0x2a, 0x3a, 0x2b, 0xc9,
+                    0x47, 0x61, 0x02, 0xbb, 0x28, 0x82, 0x34, 0xc4,
+                    0x15, 0xa2, 0xb0, 0x1f, 0x82, 0x8e, 0xa6, 0x2a,
+                    0xc5, 0xb3, 0xe4, 0x2f,
+                ],
+                output_str: "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f"
+            },
+            Test {
+                input: "abcdef",
+                output: [
+                    0x70, 0x43, 0x63, 0x1c, 0xb4, 0x15, 0x55, 0x6a,
+                    0x27, 0x5a, 0x4e, 0xbe, 0xcb, 0x80, 0x2c, 0x74,
+                    0xee, 0x9f, 0x61, 0x53, 0x90, 0x8e, 0x17, 0x92,
+                    0xa9, 0x0b, 0x6a, 0x98,
+                ],
+                output_str: "7043631cb415556a275a4ebecb802c74ee9f6153908e1792a90b6a98",
+            },
+            Test {
+                input: "Discard medicine more than two years old.",
+                output: [
+                    0x19, 0x29, 0x7f, 0x1c, 0xef, 0x7d, 0xdc, 0x8a,
+                    0x7e, 0x94, 0x7f, 0x5c, 0x5a, 0x34, 0x1e, 0x10,
+                    0xf7, 0x24, 0x50, 0x45, 0xe4, 0x25, 0xdb, 0x67,
+                    0x04, 0x39, 0x88, 0xd7,
+                ],
+                output_str: "19297f1cef7ddc8a7e947f5c5a341e10f7245045e425db67043988d7",
+            },
+            Test {
+                input: "There is no reason for any individual to have a computer in their home. -Ken Olsen, 1977",
+                output: [
+                    0x2f, 0xc3, 0x33, 0x71, 0x39, 0x83, 0xed, 0xfd,
+                    0x4e, 0xf2, 0xc0, 0xda, 0x6f, 0xb6, 0xd6, 0x41,
+                    0x5a, 0xfb, 0x94, 0x98, 0x7c, 0x91, 0xe4, 0x06,
+                    0x9e, 0xb0, 0x63, 0xe6,
+                ],
+                output_str: "2fc333713983edfd4ef2c0da6fb6d6415afb94987c91e4069eb063e6",
+            },
+            Test {
+                input: "The major problem is with sendmail.  -Mark Horton",
+                output: [
+                    0xd1, 0x07, 0x87, 0xe2, 0x40, 0x52, 0xbc, 0xff,
+                    0x26, 0xdc, 0x48, 0x47, 0x87, 0xa5, 0x4e, 0xd8,
+                    0x19, 0xe4, 0xe4, 0x51, 0x1c, 0x54, 0x89, 0x0e,
+                    0xe9, 0x77, 0xbf, 0x81,
+                ],
+                output_str: "d10787e24052bcff26dc484787a54ed819e4e4511c54890ee977bf81",
+            },
+        ];
+
+        for test in tests {
+            // Hash through high-level API, check hex encoding/decoding
+            let hash = sha224::Hash::hash(test.input.as_bytes());
+            assert_eq!(hash, test.output_str.parse::<sha224::Hash>().expect("parse hex"));
+            assert_eq!(hash.as_byte_array(), &test.output);
+            assert_eq!(hash.to_string(), test.output_str);
+
+            // Hash through engine, checking that we can input byte by byte
+            let mut engine = sha224::Hash::engine();
+            for ch in test.input.as_bytes() {
+                engine.input(&[*ch]);
+            }
+            let manual_hash = sha224::Hash::from_engine(engine);
+            assert_eq!(hash, manual_hash);
+            assert_eq!(hash.to_byte_array(), test.output);
+        }
+    }
+}
+
+#[cfg(bench)]
+mod benches {
+    use test::Bencher;
+
+    use crate::{sha224, Hash, HashEngine};
+
+    #[bench]
+    pub fn sha224_10(bh: &mut Bencher) {
+        let mut engine = sha224::Hash::engine();
+        let bytes = [1u8; 10];
+        bh.iter(|| {
+            engine.input(&bytes);
+        });
+        bh.bytes = bytes.len() as u64;
+    }
+
+    #[bench]
+    pub fn sha224_1k(bh: &mut Bencher) {
+        let mut engine = sha224::Hash::engine();
+        let bytes = [1u8; 1024];
+        bh.iter(|| {
+            engine.input(&bytes);
+        });
+        bh.bytes = bytes.len() as u64;
+    }
+
+    #[bench]
+    pub fn sha224_64k(bh: &mut Bencher) {
+        let mut engine = sha224::Hash::engine();
+        let bytes = [1u8; 65536];
+        bh.iter(|| {
+            engine.input(&bytes);
+        });
+        bh.bytes = bytes.len() as u64;
+    }
+}
@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 use test::Bencher;
 
 use crate::{sha256, Hash, HashEngine};
@@ -31,3 +33,49 @@ pub fn sha256_64k(bh: &mut Bencher) {
     });
     bh.bytes = bytes.len() as u64;
 }
+
+#[bench]
+pub fn sha256_hash_8(bh: &mut Bencher) {
+    let bytes = [1u8; 8];
+    bh.iter(|| sha256::Hash::hash(&bytes));
+    bh.bytes = bytes.len() as u64;
+}
+
+#[bench]
+pub fn sha256_hash_16(bh: &mut Bencher) {
+    let bytes = [1u8; 16];
+    bh.iter(|| sha256::Hash::hash(&bytes));
+    bh.bytes = bytes.len() as u64;
+}
+
+#[bench]
+pub fn sha256_hash_32(bh: &mut Bencher) {
+    // A Merkle leaf.
+    let bytes = [1u8; 32];
+    bh.iter(|| sha256::Hash::hash(&bytes));
+    bh.bytes = bytes.len() as u64;
+}
+
+#[bench]
+pub fn sha256_hash_64(bh: &mut Bencher) {
+    let bytes = [1u8; 64];
+    bh.iter(|| sha256::Hash::hash(&bytes));
+    bh.bytes = bytes.len() as u64;
+}
+
+#[bench]
+pub fn sha256_hash_many_32(bh: &mut Bencher) {
+    // A batch of Merkle leaves.
+    let bytes = [1u8; 32 * 1024];
+    let inputs: Vec<&[u8]> = bytes.chunks_exact(32).collect();
+    bh.iter(|| sha256::hash_many(&inputs));
+    bh.bytes = bytes.len() as u64;
+}
+
+#[bench]
+pub fn sha256_hash_many_64(bh: &mut Bencher) {
+    let bytes = [1u8; 64 * 1024];
+    let inputs: Vec<&[u8]> = bytes.chunks_exact(64).collect();
+    bh.iter(|| sha256::hash_many(&inputs));
+    bh.bytes = bytes.len() as u64;
+}
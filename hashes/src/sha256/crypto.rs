@@ -7,7 +7,7 @@
 
 use internals::slice::SliceExt;
 
-use super::{HashEngine, Midstate, BLOCK_SIZE};
+use super::{HashEngine, Midstate, BLOCK_SIZE, INITIAL_STATE};
 
 #[allow(non_snake_case)]
 const fn Ch(x: u32, y: u32, z: u32) -> u32 { z ^ (x & (y ^ z)) }
@@ -99,16 +99,7 @@ const fn read_u32(bytes: &[u8], index: usize) -> u32 {
     }
 
     pub(super) const fn compute_midstate_unoptimized(bytes: &[u8], finalize: bool) -> Self {
-        let mut state = [
-            0x6a09e667u32,
-            0xbb67ae85,
-            0x3c6ef372,
-            0xa54ff53a,
-            0x510e527f,
-            0x9b05688c,
-            0x1f83d9ab,
-            0x5be0cd19,
-        ];
+        let mut state = INITIAL_STATE;
 
         let num_chunks = (bytes.len() + 9 + 63) / 64;
         let mut chunk = 0;
@@ -247,15 +238,166 @@ pub(super) const fn compute_midstate_unoptimized(bytes: &[u8], finalize: bool) -
     }
 }
 
+/// Runs `detect` once and caches its result in `cache` so it isn't re-evaluated for every block.
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+fn cached_feature_detection(cache: &core::sync::atomic::AtomicU8, detect: fn() -> bool) -> bool {
+    use core::sync::atomic::Ordering;
+
+    const UNAVAILABLE: u8 = 1;
+    const AVAILABLE: u8 = 2;
+
+    match cache.load(Ordering::Relaxed) {
+        UNAVAILABLE => false,
+        AVAILABLE => true,
+        _ => {
+            let available = detect();
+            // Racing threads compute the same value so a relaxed store is fine.
+            cache.store(if available { AVAILABLE } else { UNAVAILABLE }, Ordering::Relaxed);
+            available
+        }
+    }
+}
+
+/// Returns `true` if the CPU supports the instructions used by the x86 SHA-NI code path.
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+pub(super) fn x86_sha_ni_available() -> bool {
+    static DETECTED: core::sync::atomic::AtomicU8 = core::sync::atomic::AtomicU8::new(0);
+
+    cached_feature_detection(&DETECTED, || {
+        std::is_x86_feature_detected!("sse4.1")
+            && std::is_x86_feature_detected!("sha")
+            && std::is_x86_feature_detected!("sse2")
+            && std::is_x86_feature_detected!("ssse3")
+    })
+}
+
+/// Returns `true` if the CPU supports the instructions used by [`hash_8_avx2`].
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+pub(super) fn x86_avx2_available() -> bool {
+    static DETECTED: core::sync::atomic::AtomicU8 = core::sync::atomic::AtomicU8::new(0);
+
+    cached_feature_detection(&DETECTED, || std::is_x86_feature_detected!("avx2"))
+}
+
+/// Hashes eight messages of equal length in parallel, one message per 32-bit AVX2 lane.
+///
+/// # Safety
+///
+/// The CPU must support AVX2, see [`x86_avx2_available`].
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn hash_8_avx2(msgs: &[&[u8]; 8]) -> [[u8; 32]; 8] {
+    macro_rules! add {
+        ($x:expr $(, $rest:expr)+) => {{
+            let sum = $x;
+            $(let sum = _mm256_add_epi32(sum, $rest);)+
+            sum
+        }};
+    }
+    macro_rules! rotr {
+        ($x:expr, $n:literal) => {
+            _mm256_or_si256(_mm256_srli_epi32($x, $n), _mm256_slli_epi32($x, 32 - $n))
+        };
+    }
+    macro_rules! xor3 {
+        ($x:expr, $y:expr, $z:expr) => {
+            _mm256_xor_si256(_mm256_xor_si256($x, $y), $z)
+        };
+    }
+
+    let len = msgs[0].len();
+    debug_assert!(msgs.iter().all(|msg| msg.len() == len));
+    let n_blocks = (len + 9 + 63) / 64;
+    let bit_len = len as u64 * 8;
+
+    let mut state = HashEngine::new().h.map(|word| _mm256_set1_epi32(word as i32));
+    let mut blocks = [[0u8; BLOCK_SIZE]; 8];
+    for index in 0..n_blocks {
+        // Fill each lane's block with either message data or the final padding.
+        let start = index * BLOCK_SIZE;
+        for (block, msg) in blocks.iter_mut().zip(msgs) {
+            if start + BLOCK_SIZE <= len {
+                block.copy_from_slice(&msg[start..start + BLOCK_SIZE]);
+                continue;
+            }
+            *block = [0; BLOCK_SIZE];
+            if start <= len {
+                let tail = &msg[start..];
+                block[..tail.len()].copy_from_slice(tail);
+                block[tail.len()] = 0x80;
+            }
+            if index + 1 == n_blocks {
+                block[BLOCK_SIZE - 8..].copy_from_slice(&bit_len.to_be_bytes());
+            }
+        }
+
+        let mut w = [_mm256_setzero_si256(); 64];
+        for (i, w_val) in w.iter_mut().take(16).enumerate() {
+            let word = |lane: usize| {
+                let mut bytes = [0; 4];
+                bytes.copy_from_slice(&blocks[lane][i * 4..i * 4 + 4]);
+                u32::from_be_bytes(bytes) as i32
+            };
+            *w_val = _mm256_setr_epi32(
+                word(0),
+                word(1),
+                word(2),
+                word(3),
+                word(4),
+                word(5),
+                word(6),
+                word(7),
+            );
+        }
+        for i in 16..64 {
+            let (w15, w2) = (w[i - 15], w[i - 2]);
+            let s0 = xor3!(rotr!(w15, 7), rotr!(w15, 18), _mm256_srli_epi32(w15, 3));
+            let s1 = xor3!(rotr!(w2, 17), rotr!(w2, 19), _mm256_srli_epi32(w2, 10));
+            w[i] = add!(w[i - 16], s0, w[i - 7], s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for (k, w_val) in super::ROUND_CONSTANTS.iter().zip(w) {
+            let sigma1 = xor3!(rotr!(e, 6), rotr!(e, 11), rotr!(e, 25));
+            let ch = _mm256_xor_si256(g, _mm256_and_si256(e, _mm256_xor_si256(f, g)));
+            let t1 = add!(h, sigma1, ch, _mm256_set1_epi32(*k as i32), w_val);
+            let sigma0 = xor3!(rotr!(a, 2), rotr!(a, 13), rotr!(a, 22));
+            let maj =
+                _mm256_or_si256(_mm256_and_si256(a, b), _mm256_and_si256(c, _mm256_or_si256(a, b)));
+            let t2 = add!(sigma0, maj);
+
+            h = g;
+            g = f;
+            f = e;
+            e = add!(d, t1);
+            d = c;
+            c = b;
+            b = a;
+            a = add!(t1, t2);
+        }
+        for (word, val) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *word = add!(*word, val);
+        }
+    }
+
+    let mut out = [[0u8; 32]; 8];
+    for (i, word) in state.iter().enumerate() {
+        let mut lanes = [0u32; 8];
+        // CAST SAFETY: storeu_si256 documentation states that mem_addr does not
+        // need to be aligned on any particular boundary.
+        _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, *word);
+        for (digest, lane) in out.iter_mut().zip(lanes) {
+            digest[i * 4..i * 4 + 4].copy_from_slice(&lane.to_be_bytes());
+        }
+    }
+    out
+}
+
 impl HashEngine {
     pub(super) fn process_block(&mut self) {
         #[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
         {
-            if std::is_x86_feature_detected!("sse4.1")
-                && std::is_x86_feature_detected!("sha")
-                && std::is_x86_feature_detected!("sse2")
-                && std::is_x86_feature_detected!("ssse3")
-            {
+            if x86_sha_ni_available() {
                 return unsafe { self.process_block_simd_x86_intrinsics() };
             }
         }
@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! SHA256 length-extension attack helper.
+//!
+//! Given `H(secret || message)` and the length of `secret || message`, but not the secret itself,
+//! anyone can compute `H(secret || message || padding || suffix)`. This is why `H(secret || m)`
+//! must never be used as a MAC, use [`crate::Hmac`] instead.
+//!
+//! This module exists to demonstrate that weakness in test suites and teaching material. It is
+//! only available with the `test-utils` feature and must not be used to build anything real.
+
+use alloc::vec::Vec;
+
+use super::{Hash, HashEngine, Midstate, BLOCK_SIZE};
+use crate::HashEngine as _;
+
+/// Forges the hash of the original message extended with `suffix`.
+///
+/// `midstate` holds the known digest (its `bytes_hashed` is ignored) and `original_len_bytes` is
+/// the total length of the original hashed message, including any secret prefix.
+///
+/// Returns the forged hash together with the glue padding, which has to be appended to the
+/// original message (followed by `suffix`) for the forged hash to be its real hash.
+pub fn extend(midstate: Midstate, original_len_bytes: u64, suffix: &[u8]) -> (Hash, Vec<u8>) {
+    let padding = glue_padding(original_len_bytes);
+    let bytes_hashed = original_len_bytes + padding.len() as u64;
+
+    let mut engine = HashEngine::from_midstate(Midstate::new(*midstate.as_parts().0, bytes_hashed));
+    engine.input(suffix);
+    (Hash::from_engine(engine), padding)
+}
+
+/// Returns the padding SHA256 appends to a message of `len` bytes before finalizing.
+fn glue_padding(len: u64) -> Vec<u8> {
+    let n_zeros = (BLOCK_SIZE * 2 - 9 - (len % BLOCK_SIZE as u64) as usize) % BLOCK_SIZE;
+
+    let mut padding = Vec::with_capacity(1 + n_zeros + 8);
+    padding.push(0x80);
+    padding.resize(1 + n_zeros, 0);
+    padding.extend_from_slice(&(8 * len).to_be_bytes());
+    padding
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forged_hash_matches_real_hash() {
+        let secret = b"super secret key";
+        let suffix = b"&admin=true";
+
+        for message_len in [0, 1, 30, 39, 40, 47, 48, 63, 64, 100] {
+            let mut original = secret.to_vec();
+            original.extend(core::iter::repeat(b'm').take(message_len));
+            let mac = Hash::hash(&original);
+
+            let midstate = Midstate::new(mac.to_byte_array(), 0);
+            let (forged, padding) = extend(midstate, original.len() as u64, suffix);
+            assert_eq!((original.len() + padding.len()) % BLOCK_SIZE, 0);
+
+            let mut extended = original;
+            extended.extend_from_slice(&padding);
+            extended.extend_from_slice(suffix);
+            assert_eq!(forged, Hash::hash(&extended));
+        }
+    }
+}
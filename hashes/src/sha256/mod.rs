@@ -5,10 +5,14 @@
 #[cfg(bench)]
 mod benches;
 mod crypto;
-#[cfg(bench)]
+#[cfg(feature = "test-utils")]
+pub mod length_extension;
+#[cfg(test)]
 mod tests;
 
-use core::{cmp, convert, fmt};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::{cmp, convert, fmt, ops};
 
 use internals::slice::SliceExt;
 
@@ -23,27 +27,27 @@
 }
 
 #[cfg(not(hashes_fuzz))]
-fn from_engine(mut e: HashEngine) -> Hash {
-    // pad buffer with a single 1-bit then all 0s, until there are exactly 8 bytes remaining
-    let n_bytes_hashed = e.bytes_hashed;
+pub(crate) fn from_engine(mut e: HashEngine) -> Hash {
+    // Pad the buffer in place, rather than going through `input`, with a single 1-bit then all
+    // 0s until there are exactly 8 bytes remaining for the big-endian length in bits.
+    const LEN_POS: usize = BLOCK_SIZE - 8;
+    let buffered = incomplete_block_len(&e);
 
-    let zeroes = [0; BLOCK_SIZE - 8];
-    e.input(&[0x80]);
-    if incomplete_block_len(&e) > zeroes.len() {
-        e.input(&zeroes);
+    e.buffer[buffered] = 0x80;
+    e.buffer[buffered + 1..].fill(0);
+    if buffered >= LEN_POS {
+        // No room left for the length, it goes in an extra block.
+        e.process_block();
+        e.buffer[..LEN_POS].fill(0);
     }
-    let pad_length = zeroes.len() - incomplete_block_len(&e);
-    e.input(&zeroes[..pad_length]);
-    debug_assert_eq!(incomplete_block_len(&e), zeroes.len());
-
-    e.input(&(8 * n_bytes_hashed).to_be_bytes());
-    debug_assert_eq!(incomplete_block_len(&e), 0);
+    e.buffer[LEN_POS..].copy_from_slice(&(8 * e.bytes_hashed).to_be_bytes());
+    e.process_block();
 
     Hash(e.midstate_unchecked().bytes)
 }
 
 #[cfg(hashes_fuzz)]
-fn from_engine(e: HashEngine) -> Hash {
+pub(crate) fn from_engine(e: HashEngine) -> Hash {
     let mut hash = e.midstate_unchecked().bytes;
     if hash == [0; 32] {
         // Assume sha256 is secure and never generate 0-hashes (which represent invalid
@@ -53,7 +57,172 @@ fn from_engine(e: HashEngine) -> Hash {
     Hash(hash)
 }
 
-const BLOCK_SIZE: usize = 64;
+/// Hashes exactly 32 bytes, typically another digest, by compressing a single fixed block.
+///
+/// The padded message always fits in one block so this skips the buffering and padding logic of
+/// the engine. Used to compute the outer hash of `sha256d`.
+#[cfg(not(hashes_fuzz))]
+pub(crate) fn hash_32(data: &[u8; 32]) -> Hash {
+    let mut engine = HashEngine::new();
+    engine.buffer[..32].copy_from_slice(data);
+    engine.buffer[32] = 0x80;
+    // Big-endian message length in bits (256), the bytes in between are already zero.
+    engine.buffer[62] = 0x01;
+    engine.process_block();
+    Hash(engine.midstate_unchecked().bytes)
+}
+
+#[cfg(hashes_fuzz)]
+pub(crate) fn hash_32(data: &[u8; 32]) -> Hash { hash(data) }
+
+/// Applies the SHA256 compression function to `state` with a single 64 byte `block`.
+///
+/// This is the raw primitive used by [`HashEngine`], starting from [`INITIAL_STATE`]. No padding
+/// or length encoding is done, callers building their own constructions are responsible for
+/// that. Prefer [`HashEngine`] or [`Midstate::absorb_block`] unless the raw state is needed.
+pub fn compress(state: &mut [u32; 8], block: &[u8; BLOCK_SIZE]) {
+    let mut engine = HashEngine { buffer: *block, h: *state, bytes_hashed: BLOCK_SIZE as u64 };
+    engine.process_block();
+    *state = engine.h;
+}
+
+/// Hashes each of the `inputs` independently, returning the hashes in the same order.
+///
+/// On x86_64 CPUs supporting AVX2 but not the SHA extensions (and with the `std` feature
+/// enabled), batches of eight inputs of equal length are hashed in parallel. Otherwise this is
+/// equivalent to calling [`Hash::hash`] on each input in turn.
+#[cfg(feature = "alloc")]
+pub fn hash_many(inputs: &[&[u8]]) -> Vec<Hash> {
+    let mut hashes = Vec::with_capacity(inputs.len());
+
+    // Hashing one input at a time with the SHA extensions beats eight AVX2 lanes.
+    #[cfg(all(feature = "std", target_arch = "x86_64", not(hashes_fuzz)))]
+    let inputs = if crypto::x86_avx2_available() && !crypto::x86_sha_ni_available() {
+        let mut batches = inputs.chunks_exact(8);
+        for batch in &mut batches {
+            let batch = <&[&[u8]; 8]>::try_from(batch).expect("chunks_exact yields 8 items");
+            if batch.iter().all(|input| input.len() == batch[0].len()) {
+                // SAFETY: We checked above that the CPU supports AVX2.
+                let digests = unsafe { crypto::hash_8_avx2(batch) };
+                hashes.extend(digests.iter().map(|digest| Hash(*digest)));
+            } else {
+                hashes.extend(batch.iter().map(|input| hash(input)));
+            }
+        }
+        batches.remainder()
+    } else {
+        inputs
+    };
+
+    hashes.extend(inputs.iter().map(|input| hash(input)));
+    hashes
+}
+
+/// Hashes `bytes`, feeding them into the engine `chunk` bytes at a time.
+///
+/// The result is identical to [`Hash::hash`], the chunk size only controls how much data each
+/// call to `input` processes (e.g. to tune for cache size when hashing a large mapped region).
+///
+/// # Panics
+///
+/// If `chunk` is zero.
+pub fn hash_chunked(bytes: &[u8], chunk: usize) -> Hash {
+    assert!(chunk != 0, "chunk size must be non-zero");
+
+    let mut engine = HashEngine::new();
+    for piece in bytes.chunks(chunk) {
+        engine.input(piece);
+    }
+    from_engine(engine)
+}
+
+/// Hashes the remaining bytes of `buf`, which need not be contiguous in memory.
+///
+/// Each contiguous chunk is input directly so the data is never copied into a single buffer.
+/// The result is identical to hashing the logical byte sequence with [`Hash::hash`].
+#[cfg(feature = "bytes")]
+pub fn hash_buf<B: bytes::Buf>(mut buf: B) -> Hash {
+    let mut engine = HashEngine::new();
+    while buf.has_remaining() {
+        let chunk = buf.chunk();
+        let len = chunk.len();
+        engine.input(chunk);
+        buf.advance(len);
+    }
+    from_engine(engine)
+}
+
+/// Hashes `prefix || len || payload`, where `len` is the length of `payload` as a little-endian
+/// `u32`.
+///
+/// # Errors
+///
+/// If the length of `payload` does not fit in a `u32`.
+pub fn hash_framed(prefix: &[u8], payload: &[u8]) -> Result<Hash, PayloadLengthError> {
+    let len = u32::try_from(payload.len())
+        .map_err(|_| PayloadLengthError { len: payload.len() as u64 })?;
+
+    let mut engine = HashEngine::new();
+    engine.input(prefix);
+    engine.input(&len.to_le_bytes());
+    engine.input(payload);
+    Ok(from_engine(engine))
+}
+
+/// Hashes `bytes` without finalizing, returning the midstate.
+///
+/// Equivalent to inputting `bytes` into a new engine and calling [`HashEngine::midstate`].
+///
+/// # Errors
+///
+/// If the length of `bytes` is not a multiple of 64.
+pub fn hash_to_midstate(bytes: &[u8]) -> Result<Midstate, MidstateError> {
+    let mut engine = HashEngine::new();
+    engine.input(bytes);
+    engine.midstate()
+}
+
+/// Returns an iterator over the midstates after each complete 64 byte block of `data`.
+///
+/// Trailing bytes that don't fill a block are ignored. If the length of `data` is a multiple of
+/// 64, the last item is the midstate returned by [`hash_to_midstate`].
+pub fn block_midstates(data: &[u8]) -> impl Iterator<Item = Midstate> + '_ {
+    let mut midstate = HashEngine::new().midstate_unchecked();
+    data.bitcoin_as_chunks::<BLOCK_SIZE>().0.iter().map(move |block| {
+        midstate.absorb_block(block);
+        midstate
+    })
+}
+
+/// Computes a BIP-340 style tagged hash of `msg`, given the midstate of the tag.
+///
+/// `tag_midstate` is the state after hashing `sha256(tag) || sha256(tag)`, as computed by
+/// [`Midstate::hash_tag`]. For a tag known at compile time prefer defining a [`sha256t::Hash`].
+pub fn hash_tagged(tag_midstate: &Midstate, msg: &[u8]) -> Hash {
+    let mut engine = HashEngine::from_midstate(*tag_midstate);
+    engine.input(msg);
+    from_engine(engine)
+}
+
+pub(crate) const BLOCK_SIZE: usize = 64;
+
+/// The SHA256 initial hash value (FIPS 180-4, section 5.3.3).
+pub const INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// The SHA256 round constants (FIPS 180-4, section 4.2.2).
+#[rustfmt::skip]
+pub const ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
 
 /// Engine to compute SHA256 hash function.
 #[derive(Debug, Clone)]
@@ -66,10 +235,15 @@ pub struct HashEngine {
 impl HashEngine {
     /// Constructs a new SHA256 hash engine.
     pub const fn new() -> Self {
+        Self { h: INITIAL_STATE, bytes_hashed: 0, buffer: [0; BLOCK_SIZE] }
+    }
+
+    /// Constructs a new hash engine suitable for constructing a `sha224::HashEngine`.
+    pub(crate) const fn sha224() -> Self {
         Self {
             h: [
-                0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
-                0x5be0cd19,
+                0xc1059ed8, 0x367cd507, 0x3070dd17, 0xf70e5939, 0xffc00b31, 0x68581511, 0x64f98fa7,
+                0xbefa4fa4,
             ],
             bytes_hashed: 0,
             buffer: [0; BLOCK_SIZE],
@@ -89,6 +263,54 @@ pub fn from_midstate(midstate: Midstate) -> HashEngine {
         HashEngine { buffer: [0; BLOCK_SIZE], h: ret, bytes_hashed: midstate.bytes_hashed }
     }
 
+    /// Resets the engine to the state of a freshly constructed [`HashEngine::new`].
+    ///
+    /// This allows reusing a single engine to hash many independent messages.
+    pub fn reset(&mut self) { *self = Self::new(); }
+
+    /// Produces a hash from the current state of the engine and resets it for reuse.
+    ///
+    /// This is equivalent to calling [`Hash::from_engine`] on a clone of the engine and then
+    /// calling [`HashEngine::reset`].
+    pub fn finalize_reset(&mut self) -> Hash { Hash::from_engine(core::mem::take(self)) }
+
+    /// Adds data to the engine and returns the number of bytes absorbed.
+    ///
+    /// This behaves exactly like [`crate::HashEngine::input`]; the engine always consumes the
+    /// whole slice so the returned count is `bytes.len()`.
+    pub fn input_with_count(&mut self, bytes: &[u8]) -> usize {
+        self.input(bytes);
+        bytes.len()
+    }
+
+    /// Adds data to the engine, accepting anything that can be viewed as bytes.
+    ///
+    /// Forwards to [`crate::HashEngine::input`], so arrays, vectors and strings can be passed
+    /// without an explicit borrow or `as_ref` call.
+    pub fn feed<B: AsRef<[u8]>>(&mut self, data: B) { self.input(data.as_ref()) }
+
+    /// Adds data to the engine and returns it, for builder-style one-liners.
+    ///
+    /// `HashEngine::new().chain(a).chain(b).finalize()` hashes the concatenation of `a` and `b`,
+    /// using [`crate::HashEngine::finalize`].
+    #[must_use]
+    pub fn chain(mut self, data: &[u8]) -> Self {
+        self.input(data);
+        self
+    }
+
+    /// Returns the input bytes that have not yet filled a complete block.
+    ///
+    /// The returned slice is `n_bytes_hashed() % 64` bytes long, so it is empty exactly when
+    /// [`Self::can_extract_midstate`] returns `true`.
+    pub fn buffered_bytes(&self) -> &[u8] { &self.buffer[..self.buffered_len()] }
+
+    /// Returns the number of complete 64 byte blocks compressed so far.
+    pub const fn blocks_processed(&self) -> u64 { self.bytes_hashed / BLOCK_SIZE as u64 }
+
+    /// Returns the number of input bytes buffered waiting for a complete block.
+    pub const fn buffered_len(&self) -> usize { (self.bytes_hashed % BLOCK_SIZE as u64) as usize }
+
     /// Returns `true` if the midstate can be extracted from this engine.
     ///
     /// The midstate can only be extracted if the number of bytes input into
@@ -107,6 +329,73 @@ pub fn midstate(&self) -> Result<Midstate, MidstateError> {
         Ok(self.midstate_unchecked())
     }
 
+    /// Writes the midstate of the hash engine into `out`, returning the number of bytes hashed.
+    ///
+    /// This is [`Self::midstate`] writing into a caller-provided buffer, e.g. to reuse a single
+    /// buffer across many checkpoints.
+    ///
+    /// # Errors
+    ///
+    /// If the number of bytes input into the engine is not a multiple of 64.
+    pub fn write_state(&self, out: &mut [u8; 32]) -> Result<u64, MidstateError> {
+        let midstate = self.midstate()?;
+        *out = midstate.bytes;
+        Ok(midstate.bytes_hashed)
+    }
+
+    /// Outputs the midstate of the completed blocks along with the buffered partial block.
+    ///
+    /// Unlike [`Self::midstate`] this works at any point in the stream. Inputting the returned
+    /// bytes into an engine constructed with [`Self::from_midstate`] reproduces this engine.
+    ///
+    /// Please see docs on [`Midstate`] before using this function.
+    pub fn split_midstate(&self) -> (Midstate, &[u8]) {
+        let buffered = self.buffered_bytes();
+        let mut midstate = self.midstate_unchecked();
+        midstate.bytes_hashed -= buffered.len() as u64;
+        (midstate, buffered)
+    }
+
+    /// Serializes the full state of the engine, including any partially filled block.
+    ///
+    /// The snapshot consists of the 32 byte state, the big-endian number of bytes hashed and the
+    /// 64 byte block buffer. Unlike [`Self::midstate`] this works at any point in the stream.
+    /// Bytes of the buffer beyond the partially filled block are written as zero.
+    pub fn to_snapshot(&self) -> [u8; 104] {
+        let mut ret = [0; 104];
+        for (val, ret_bytes) in self.h.iter().zip(ret[..32].bitcoin_as_chunks_mut::<4>().0) {
+            *ret_bytes = val.to_be_bytes();
+        }
+        ret[32..40].copy_from_slice(&self.bytes_hashed.to_be_bytes());
+        let buffered = incomplete_block_len(self);
+        ret[40..40 + buffered].copy_from_slice(&self.buffer[..buffered]);
+        ret
+    }
+
+    /// Restores an engine previously serialized with [`Self::to_snapshot`].
+    ///
+    /// # Errors
+    ///
+    /// If any of the buffer bytes beyond the partially filled block are non-zero.
+    pub fn from_snapshot(snapshot: &[u8; 104]) -> Result<HashEngine, SnapshotError> {
+        let mut h = [0; 8];
+        for (val, bytes) in h.iter_mut().zip(snapshot[..32].bitcoin_as_chunks::<4>().0) {
+            *val = u32::from_be_bytes(*bytes);
+        }
+        let mut bytes_hashed = [0; 8];
+        bytes_hashed.copy_from_slice(&snapshot[32..40]);
+        let bytes_hashed = u64::from_be_bytes(bytes_hashed);
+
+        let buffered = (bytes_hashed % BLOCK_SIZE as u64) as usize;
+        if snapshot[40 + buffered..].iter().any(|&b| b != 0) {
+            return Err(SnapshotError { n_bytes_hashed: bytes_hashed });
+        }
+        let mut buffer = [0; BLOCK_SIZE];
+        buffer.copy_from_slice(&snapshot[40..]);
+
+        Ok(HashEngine { buffer, h, bytes_hashed })
+    }
+
     // Does not check that `HashEngine::can_extract_midstate`.
     #[cfg(not(hashes_fuzz))]
     fn midstate_unchecked(&self) -> Midstate {
@@ -140,10 +429,299 @@ fn n_bytes_hashed(&self) -> u64 { self.bytes_hashed }
     fn finalize(self) -> Self::Hash { Hash::from_engine(self) }
 }
 
+/// Hashes the UTF-8 bytes of formatted text without allocating an intermediate `String`.
+impl fmt::Write for HashEngine {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.input(s.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::HashMarker for HashEngine {}
+
+#[cfg(feature = "digest")]
+impl digest::OutputSizeUser for HashEngine {
+    type OutputSize = digest::consts::U32;
+}
+
+#[cfg(feature = "digest")]
+impl digest::Update for HashEngine {
+    fn update(&mut self, data: &[u8]) { self.input(data) }
+}
+
+#[cfg(feature = "digest")]
+impl digest::FixedOutput for HashEngine {
+    fn finalize_into(self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(Hash::from_engine(self).as_byte_array())
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::Reset for HashEngine {
+    fn reset(&mut self) { HashEngine::reset(self) }
+}
+
+#[cfg(feature = "digest")]
+impl digest::FixedOutputReset for HashEngine {
+    fn finalize_into_reset(&mut self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(self.finalize_reset().as_byte_array())
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for HashEngine {
+    fn zeroize(&mut self) {
+        self.buffer.zeroize();
+        self.h.zeroize();
+        self.bytes_hashed.zeroize();
+    }
+}
+
+/// Wipes the block buffer and the intermediate state so that no secret-dependent data outlives
+/// the engine.
+#[cfg(feature = "zeroize")]
+impl Drop for HashEngine {
+    fn drop(&mut self) { zeroize::Zeroize::zeroize(self) }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for HashEngine {}
+
+/// Engine to compute SHA256 hash function over input of bounded total length.
+///
+/// Useful when hashing data from an untrusted source that is supposed to be of bounded size, e.g.
+/// a header, to fail fast rather than process an arbitrarily long stream.
+///
+/// Use [`Self::try_input`] to get an error once the limit would be exceeded, the
+/// [`crate::HashEngine::input`] implementation panics instead.
+#[derive(Debug, Clone)]
+pub struct BoundedHashEngine {
+    engine: HashEngine,
+    max_len: u64,
+}
+
+impl BoundedHashEngine {
+    /// Constructs a new SHA256 hash engine accepting at most `max_len` bytes of input.
+    pub const fn new(max_len: u64) -> Self { Self { engine: HashEngine::new(), max_len } }
+
+    /// Returns the maximum number of bytes this engine accepts.
+    pub const fn max_len(&self) -> u64 { self.max_len }
+
+    /// Returns the number of bytes that can still be input.
+    pub const fn remaining(&self) -> u64 { self.max_len - self.engine.bytes_hashed }
+
+    /// Adds data to the engine if the total input stays within the limit.
+    ///
+    /// # Errors
+    ///
+    /// If inputting `data` would exceed the limit, in which case none of `data` is input.
+    pub fn try_input(&mut self, data: &[u8]) -> Result<(), InputLimitError> {
+        if data.len() as u64 > self.remaining() {
+            return Err(InputLimitError {
+                max_len: self.max_len,
+                attempted_len: self.engine.bytes_hashed.saturating_add(data.len() as u64),
+            });
+        }
+        self.engine.input(data);
+        Ok(())
+    }
+
+    /// Returns the unbounded engine containing the input so far.
+    pub fn into_inner(self) -> HashEngine { self.engine }
+}
+
+impl crate::HashEngine for BoundedHashEngine {
+    type Hash = Hash;
+    type Bytes = [u8; 32];
+    const BLOCK_SIZE: usize = 64;
+
+    /// Adds data to the engine.
+    ///
+    /// # Panics
+    ///
+    /// If the total input would exceed the limit, see [`BoundedHashEngine::try_input`].
+    fn input(&mut self, data: &[u8]) {
+        if let Err(e) = self.try_input(data) {
+            panic!("{}", e);
+        }
+    }
+
+    fn n_bytes_hashed(&self) -> u64 { self.engine.bytes_hashed }
+    fn finalize(self) -> Self::Hash { Hash::from_engine(self.engine) }
+}
+
 impl Hash {
     /// Iterate the sha256 algorithm to turn a sha256 hash into a sha256d hash
     #[must_use]
-    pub fn hash_again(&self) -> sha256d::Hash { sha256d::Hash::from_byte_array(hash(&self.0).0) }
+    pub fn hash_again(&self) -> sha256d::Hash { sha256d::Hash::from_byte_array(hash_32(&self.0).0) }
+
+    /// Hashes the concatenation of `left` and `right`, e.g. two child nodes of a hash tree.
+    pub fn hash_pair(left: &Hash, right: &Hash) -> Hash {
+        let mut engine = HashEngine::new();
+        engine.input(&left.0);
+        engine.input(&right.0);
+        from_engine(engine)
+    }
+
+    /// Constructs a hash with all bytes set to zero.
+    ///
+    /// This is a sentinel byte pattern (e.g. for "null" parents), not the output of hashing
+    /// anything.
+    pub const fn all_zeros() -> Hash { Hash([0; 32]) }
+
+    /// Constructs a hash with all bits set to one.
+    ///
+    /// This is a sentinel byte pattern (e.g. the maximum target), not the output of hashing
+    /// anything.
+    pub const fn all_ones() -> Hash { Hash([0xff; 32]) }
+
+    /// XORs all `hashes` together byte-wise, returning [`Self::all_zeros`] if there are none.
+    ///
+    /// The result does not depend on the order of `hashes`, making it a cheap consistency check
+    /// over a set. It is not cryptographically binding: anyone can find other sets of hashes with
+    /// the same result, and any hash included twice cancels out. Do not use it as a commitment.
+    pub fn xor_fold(hashes: &[Hash]) -> Hash {
+        hashes.iter().fold(Hash::all_zeros(), |acc, hash| acc ^ *hash)
+    }
+
+    /// Computes a cheap, deterministic stand-in for the hash of `bytes`.
+    ///
+    /// Like the `hashes_fuzz` build, this never returns all zeros but is otherwise trivial (the
+    /// input and its length are XORed together) and provides no security at all. It is only meant
+    /// for tests that need hash-shaped values quickly, never use it outside of tests.
+    #[cfg(feature = "test-utils")]
+    pub fn hash_deterministic_stub(bytes: &[u8]) -> Hash {
+        let mut ret = [0; 32];
+        for (i, byte) in bytes.iter().enumerate() {
+            ret[i % 32] ^= byte;
+        }
+        for (byte, len_byte) in ret[24..].iter_mut().zip((bytes.len() as u64).to_le_bytes()) {
+            *byte ^= len_byte;
+        }
+        if ret == [0; 32] {
+            ret[0] = 1;
+        }
+        Hash(ret)
+    }
+
+    /// Hashes the concatenation of `slices`, e.g. the buffers of a vectored read.
+    #[cfg(feature = "std")]
+    pub fn hash_vectored(slices: &[std::io::IoSlice]) -> Hash {
+        hash_byte_chunks(slices.iter().map(|slice| &slice[..]))
+    }
+
+    /// Returns the first `N` bytes of the hash, e.g. to derive a short ID.
+    ///
+    /// # Panics
+    ///
+    /// If `N` is greater than 32.
+    pub fn truncate<const N: usize>(&self) -> [u8; N] {
+        assert!(N <= 32, "cannot truncate a 32 byte hash to {} bytes", N);
+        let mut ret = [0; N];
+        ret.copy_from_slice(&self.0[..N]);
+        ret
+    }
+
+    /// Returns the first 8 bytes of the hash as a little-endian `u64`.
+    pub fn truncate_to_u64(&self) -> u64 { u64::from_le_bytes(self.truncate()) }
+
+    /// Returns the underlying byte array in reverse order.
+    ///
+    /// Block explorers display block and transaction IDs in this order.
+    pub fn to_byte_array_reversed(&self) -> [u8; 32] {
+        let mut bytes = self.0;
+        bytes.reverse();
+        bytes
+    }
+
+    /// Returns a value that displays the hash as hex with the bytes in reverse order.
+    ///
+    /// With the `hex` feature enabled the output can be parsed back with `from_str_reversed`.
+    pub fn display_reversed(&self) -> DisplayReversed { DisplayReversed(*self) }
+
+    /// Parses a hex string displayed with the bytes in reverse order, e.g. by
+    /// [`Self::display_reversed`].
+    #[cfg(feature = "hex")]
+    pub fn from_str_reversed(s: &str) -> Result<Hash, hex::HexToArrayError> {
+        use hex::FromHex;
+
+        let mut bytes = <[u8; 32]>::from_hex(s)?;
+        bytes.reverse();
+        Ok(Hash(bytes))
+    }
+
+    /// Parses a hex string, accepting only the canonical all-lowercase form.
+    ///
+    /// Unlike [`core::str::FromStr`], which accepts either case, any uppercase hex digit is an
+    /// error. The bytes are in the same (forward) order as the `Display` output.
+    pub fn from_str_canonical(s: &str) -> Result<Hash, CanonicalHexError> {
+        let s = s.as_bytes();
+        if s.len() != 64 {
+            return Err(CanonicalHexError::InvalidLength { len: s.len() });
+        }
+
+        let mut ret = [0; 32];
+        for (pos, &c) in s.iter().enumerate() {
+            let nibble = match c {
+                b'0'..=b'9' => c - b'0',
+                b'a'..=b'f' => c - b'a' + 10,
+                b'A'..=b'F' => return Err(CanonicalHexError::NotLowercase { pos }),
+                _ => return Err(CanonicalHexError::InvalidChar { pos, c }),
+            };
+            let shift = if pos % 2 == 0 { 4 } else { 0 };
+            ret[pos / 2] |= nibble << shift;
+        }
+        Ok(Hash(ret))
+    }
+
+    /// Returns the hex encoding of the hash in the requested `case`.
+    ///
+    /// With [`hex::Case::Lower`] this is the same as the `Display` output.
+    #[cfg(all(feature = "alloc", feature = "hex"))]
+    pub fn to_hex_string(&self, case: hex::Case) -> alloc::string::String {
+        use hex::DisplayHex;
+
+        self.0[..].to_hex_string(case)
+    }
+
+    /// Produces a hash from the current state of `engine` without consuming it.
+    ///
+    /// The engine is left unchanged so more data can be input afterwards, continuing the stream.
+    pub fn from_engine_ref(engine: &HashEngine) -> Hash { from_engine(engine.clone()) }
+
+    /// Compares the hash with `other`, interpreting both as big-endian 256-bit integers.
+    ///
+    /// The first byte of the array is the most significant. Note that this is the order of
+    /// [`Self::as_byte_array`], not of the (possibly reversed) hex display of a hash.
+    pub fn cmp_be(&self, other: &[u8; 32]) -> cmp::Ordering {
+        // Lexicographic order on equal-length byte arrays is big-endian numeric order.
+        self.0.cmp(other)
+    }
+
+    /// Returns `true` if the hash, as a big-endian 256-bit integer, is less than or equal to
+    /// `target`.
+    ///
+    /// See [`Self::cmp_be`] for the byte order used.
+    pub fn meets_target_be(&self, target: &[u8; 32]) -> bool {
+        self.cmp_be(target) != cmp::Ordering::Greater
+    }
+
+    /// Re-hashes the digest with sha256 `iterations` times.
+    ///
+    /// With `iterations == 0` this returns `self` unchanged and with `iterations == 1` the result
+    /// has the same bytes as [`Self::hash_again`].
+    ///
+    /// This is not a substitute for a real key derivation function, it is only provided to
+    /// support formats that iterate plain SHA256.
+    #[must_use]
+    pub fn hash_again_n(&self, iterations: u32) -> Hash {
+        let mut ret = *self;
+        for _ in 0..iterations {
+            ret = hash_32(&ret.0);
+        }
+        ret
+    }
 
     /// Computes hash from `bytes` in `const` context.
     ///
@@ -159,6 +737,73 @@ pub const fn hash_unoptimized(bytes: &[u8]) -> Self {
     }
 }
 
+/// Displays a [`struct@Hash`] as hex with the bytes in reverse order.
+///
+/// Returned by [`Hash::display_reversed`].
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayReversed(Hash);
+
+impl fmt::Display for DisplayReversed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        crate::debug_hex(&self.0.to_byte_array_reversed(), f)
+    }
+}
+
+// Implements a byte-wise bitwise operator, and its assigning variant, for `Hash`.
+macro_rules! impl_bitwise_op {
+    ($op:ident, $op_fn:ident, $op_assign:ident, $op_assign_fn:ident, $assign_tok:tt) => {
+        impl ops::$op for Hash {
+            type Output = Hash;
+
+            fn $op_fn(mut self, rhs: Hash) -> Hash {
+                ops::$op_assign::$op_assign_fn(&mut self, rhs);
+                self
+            }
+        }
+
+        impl ops::$op_assign for Hash {
+            fn $op_assign_fn(&mut self, rhs: Hash) {
+                for (byte, rhs_byte) in self.0.iter_mut().zip(rhs.0) {
+                    *byte $assign_tok rhs_byte;
+                }
+            }
+        }
+    };
+}
+impl_bitwise_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^=);
+impl_bitwise_op!(BitAnd, bitand, BitAndAssign, bitand_assign, &=);
+impl_bitwise_op!(BitOr, bitor, BitOrAssign, bitor_assign, |=);
+
+/// A [`core::hash::Hasher`] for map keys that are themselves cryptographic hashes.
+///
+/// Uses the first 8 bytes of the key directly as the hash code instead of hashing all of it
+/// again. Use it through [`BuildHashHasher`], e.g. `HashMap<sha256::Hash, V, BuildHashHasher>`.
+///
+/// This is only sound for keys that are the output of a cryptographic hash function. Do not use it
+/// if an attacker can choose the keys (including by grinding inputs to the hash), since picking
+/// keys that land in the same bucket is then cheap and lookups degrade to linear time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HashHasher(u64);
+
+impl core::hash::Hasher for HashHasher {
+    fn finish(&self) -> u64 { self.0 }
+
+    fn write(&mut self, bytes: &[u8]) {
+        // Keys write a length prefix first so the last write of at least 8 bytes, the hash
+        // itself, determines the hash code. Shorter writes are folded in.
+        match bytes.get(..8) {
+            Some(prefix) => self.0 = u64::from_le_bytes(prefix.try_into().expect("8 bytes")),
+            None =>
+                for &byte in bytes {
+                    self.0 = self.0.rotate_left(8) ^ u64::from(byte);
+                },
+        }
+    }
+}
+
+/// Builds [`HashHasher`]s for use in hash maps and sets.
+pub type BuildHashHasher = core::hash::BuildHasherDefault<HashHasher>;
+
 /// Unfinalized output of the SHA256 hash function.
 ///
 /// The `Midstate` type is obscure and specialized and should not be used unless you are sure of
@@ -183,25 +828,65 @@ pub struct Midstate {
 }
 
 impl Midstate {
+    /// Returns `true` if `bytes_hashed` is a valid number of bytes hashed for a midstate, i.e. a
+    /// multiple of 64.
+    ///
+    /// Useful to check a length in `const` context before calling [`Self::new`].
+    pub const fn is_valid_bytes_hashed(bytes_hashed: u64) -> bool { bytes_hashed % 64 == 0 }
+
     /// Construct a new [`Midstate`] from the `state` and the `bytes_hashed` to get to that state.
     ///
     /// # Panics
     ///
     /// Panics if `bytes_hashed` is not a multiple of 64.
     pub const fn new(state: [u8; 32], bytes_hashed: u64) -> Self {
-        if bytes_hashed % 64 != 0 {
+        if !Self::is_valid_bytes_hashed(bytes_hashed) {
             panic!("bytes hashed is not a multiple of 64");
         }
 
         Midstate { bytes: state, bytes_hashed }
     }
 
+    /// Construct a new [`Midstate`] from the `state` and the `bytes_hashed` to get to that state.
+    ///
+    /// # Errors
+    ///
+    /// If `bytes_hashed` is not a multiple of 64.
+    pub const fn try_new(state: [u8; 32], bytes_hashed: u64) -> Result<Self, MidstateError> {
+        if !Self::is_valid_bytes_hashed(bytes_hashed) {
+            return Err(MidstateError { invalid_n_bytes_hashed: bytes_hashed });
+        }
+
+        Ok(Midstate { bytes: state, bytes_hashed })
+    }
+
     /// Deconstructs the [`Midstate`], returning the underlying byte array and number of bytes hashed.
     pub const fn as_parts(&self) -> (&[u8; 32], u64) { (&self.bytes, self.bytes_hashed) }
 
+    /// Returns the underlying state bytes.
+    pub const fn state(&self) -> &[u8; 32] { &self.bytes }
+
+    /// Returns the number of bytes hashed to get to this state.
+    pub const fn bytes_hashed(&self) -> u64 { self.bytes_hashed }
+
     /// Deconstructs the [`Midstate`], returning the underlying byte array and number of bytes hashed.
     pub const fn to_parts(self) -> ([u8; 32], u64) { (self.bytes, self.bytes_hashed) }
 
+    /// Compares two midstates for equality in `const` context, where `==` cannot be used.
+    pub const fn const_eq(&self, other: &Midstate) -> bool {
+        if self.bytes_hashed != other.bytes_hashed {
+            return false;
+        }
+        let mut i = 0;
+        while i < self.bytes.len() {
+            if self.bytes[i] != other.bytes[i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
     /// Constructs a new midstate for tagged hashes.
     ///
     /// Warning: this function is inefficient. It should be only used in `const` context.
@@ -219,6 +904,65 @@ pub const fn hash_tag(tag: &[u8]) -> Self {
         }
         Self::compute_midstate_unoptimized(&buf, false)
     }
+
+    /// Computes the midstate after hashing `prefix`.
+    ///
+    /// Warning: this function is inefficient. It should be only used in `const` context.
+    ///
+    /// The midstate is taken right after the blocks of `prefix`, ready to be resumed with
+    /// [`HashEngine::from_midstate`]. To hash a whole message in `const` context use
+    /// [`Hash::hash_unoptimized`] instead.
+    ///
+    /// # Panics
+    ///
+    /// If the length of `prefix` is not a multiple of 64, since a midstate cannot represent a
+    /// partially filled block.
+    #[must_use]
+    pub const fn compute(prefix: &[u8]) -> Self {
+        if prefix.len() % 64 != 0 {
+            panic!("prefix length is not a multiple of 64");
+        }
+        Self::compute_midstate_unoptimized(prefix, false)
+    }
+
+    /// Constructs a new midstate for tagged hashes at runtime.
+    ///
+    /// Produces the same midstate as [`Self::hash_tag`] but uses the optimized hashing code, so
+    /// prefer this when not in `const` context.
+    #[must_use]
+    pub fn hash_tag_runtime(tag: &[u8]) -> Self {
+        let hash = Hash::hash(tag);
+        let mut engine = HashEngine::new();
+        engine.buffer[..32].copy_from_slice(&hash.0);
+        engine.buffer[32..].copy_from_slice(&hash.0);
+        engine.bytes_hashed = BLOCK_SIZE as u64;
+        engine.process_block();
+        engine.midstate_unchecked()
+    }
+
+    /// Returns `true` if this is the midstate of the tagged hash with `tag`.
+    ///
+    /// Useful to check a precomputed midstate obtained from an untrusted source. The tag midstate
+    /// is recomputed on every call (see [`Self::hash_tag_runtime`]) so avoid this in hot loops.
+    pub fn matches_tag(&self, tag: &[u8]) -> bool { *self == Self::hash_tag_runtime(tag) }
+
+    /// Advances the midstate by hashing a single 64 byte `block`.
+    ///
+    /// This is equivalent to inputting `block` into an engine constructed from this midstate but
+    /// avoids the buffering of the general engine when the data is already block-aligned.
+    pub fn absorb_block(&mut self, block: &[u8; BLOCK_SIZE]) {
+        let mut engine = HashEngine::from_midstate(*self);
+        engine.buffer = *block;
+        engine.bytes_hashed += BLOCK_SIZE as u64;
+        engine.process_block();
+        *self = engine.midstate_unchecked();
+    }
+
+    /// Finalizes the hash, i.e. computes the hash of the bytes that led to this midstate.
+    ///
+    /// Since the midstate is block-aligned this only compresses the single padding block.
+    #[must_use]
+    pub fn finalize(&self) -> Hash { from_engine(HashEngine::from_midstate(*self)) }
 }
 
 impl fmt::Debug for Midstate {
@@ -241,6 +985,46 @@ impl convert::AsRef<[u8]> for Midstate {
     fn as_ref(&self) -> &[u8] { &self.bytes }
 }
 
+impl From<Midstate> for ([u8; 32], u64) {
+    fn from(midstate: Midstate) -> Self { midstate.to_parts() }
+}
+
+impl From<Midstate> for [u8; 32] {
+    fn from(midstate: Midstate) -> Self { midstate.bytes }
+}
+
+/// Serializes as a `(bytes, bytes_hashed)` tuple, with the bytes in the same format as
+/// [`struct@Hash`].
+#[cfg(feature = "serde")]
+impl serde::Serialize for Midstate {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&(Hash(self.bytes), self.bytes_hashed), s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Midstate {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Midstate, D::Error> {
+        use serde::de::Error as _;
+
+        let (bytes, bytes_hashed): (Hash, u64) = serde::Deserialize::deserialize(d)?;
+        if bytes_hashed % 64 != 0 {
+            return Err(D::Error::custom(MidstateError { invalid_n_bytes_hashed: bytes_hashed }));
+        }
+        Ok(Midstate { bytes: bytes.0, bytes_hashed })
+    }
+}
+
+/// Since [`Midstate`] is `Copy` it cannot be wiped on drop, callers holding secret-dependent
+/// midstates must call `zeroize` on every copy themselves.
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Midstate {
+    fn zeroize(&mut self) {
+        self.bytes.zeroize();
+        self.bytes_hashed.zeroize();
+    }
+}
+
 /// `Midstate` invariant violated (not a multiple of 64).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MidstateError {
@@ -260,3 +1044,98 @@ fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 
 #[cfg(feature = "std")]
 impl std::error::Error for MidstateError {}
+
+/// Engine snapshot is invalid (non-zero bytes beyond the partially filled block).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotError {
+    /// The number of bytes hashed recorded in the snapshot.
+    n_bytes_hashed: u64,
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid engine snapshot: non-zero buffer bytes beyond the {} buffered bytes",
+            self.n_bytes_hashed % BLOCK_SIZE as u64
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SnapshotError {}
+
+/// Input to a [`BoundedHashEngine`] would exceed its limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputLimitError {
+    /// The maximum number of bytes the engine accepts.
+    max_len: u64,
+    /// The total number of bytes that would have been input.
+    attempted_len: u64,
+}
+
+impl fmt::Display for InputLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "input of {} bytes in total exceeds the limit of {} bytes",
+            self.attempted_len, self.max_len
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InputLimitError {}
+
+/// Payload passed to [`hash_framed`] is too long for its length to be encoded as a `u32`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayloadLengthError {
+    /// The length of the payload.
+    len: u64,
+}
+
+impl fmt::Display for PayloadLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "payload length {} does not fit in a u32", self.len)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PayloadLengthError {}
+
+/// Error parsing a hash with [`Hash::from_str_canonical`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CanonicalHexError {
+    /// The string is not 64 bytes long.
+    InvalidLength {
+        /// The length of the string, in bytes.
+        len: usize,
+    },
+    /// A byte of the string is not a hex digit.
+    InvalidChar {
+        /// The position of the offending byte.
+        pos: usize,
+        /// The offending byte.
+        c: u8,
+    },
+    /// A hex digit is uppercase.
+    NotLowercase {
+        /// The position of the offending digit.
+        pos: usize,
+    },
+}
+
+impl fmt::Display for CanonicalHexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use CanonicalHexError::*;
+
+        match *self {
+            InvalidLength { len } => write!(f, "invalid hex length {} (expected 64)", len),
+            InvalidChar { pos, c } => write!(f, "invalid hex character {:#04x} at {}", c, pos),
+            NotLowercase { pos } => write!(f, "non-lowercase hex digit at {}", pos),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CanonicalHexError {}
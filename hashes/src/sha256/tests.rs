@@ -162,6 +162,503 @@ fn engine_with_state() {
     assert_eq!(hash, sha256::Hash(HASH_EXPECTED));
 }
 
+#[test]
+fn engine_reset() {
+    let mut engine = sha256::Hash::engine();
+    engine.input(&[1; 100]);
+    assert!(!engine.can_extract_midstate());
+
+    engine.reset();
+    assert!(engine.can_extract_midstate());
+    assert_eq!(engine.h, sha256::HashEngine::new().h);
+    assert_eq!(engine.bytes_hashed, 0);
+    assert_eq!(engine.buffer, [0; BLOCK_SIZE]);
+
+    engine.input(b"abc");
+    assert_eq!(sha256::Hash::from_engine(engine), sha256::Hash::hash(b"abc"));
+}
+
+#[test]
+fn engine_finalize_reset() {
+    let mut engine = sha256::Hash::engine();
+    for data in [&b"abc"[..], &[7; 64], &[8; 200], &[]] {
+        engine.input(data);
+        let want = sha256::Hash::from_engine(engine.clone());
+        assert_eq!(engine.finalize_reset(), want);
+        assert_eq!(engine.bytes_hashed, 0);
+        assert_eq!(engine.h, sha256::HashEngine::new().h);
+        assert_eq!(engine.buffer, [0; BLOCK_SIZE]);
+        assert_eq!(want, sha256::Hash::hash(data));
+    }
+}
+
+#[test]
+fn hash_32_matches_generic_path() {
+    for byte in [0x00, 0x5a, 0xff] {
+        let data = [byte; 32];
+        assert_eq!(sha256::hash_32(&data), sha256::Hash::hash(&data));
+    }
+    let data = sha256::Hash::hash(b"abc");
+    let want = sha256::Hash::hash(data.as_ref());
+    assert_eq!(data.hash_again().to_byte_array(), want.to_byte_array());
+}
+
+#[test]
+fn hash_again_n() {
+    let hash = sha256::Hash::hash(b"abc");
+    assert_eq!(hash.hash_again_n(0), hash);
+    assert_eq!(hash.hash_again_n(1).to_byte_array(), hash.hash_again().to_byte_array());
+
+    let mut want = hash;
+    for _ in 0..5 {
+        want = sha256::Hash::hash(want.as_ref());
+    }
+    assert_eq!(hash.hash_again_n(5), want);
+}
+
+#[test]
+fn bitwise_ops() {
+    let a = sha256::Hash::from_byte_array([0b1100; 32]);
+    let b = sha256::Hash::from_byte_array([0b1010; 32]);
+
+    assert_eq!(a ^ b, sha256::Hash::from_byte_array([0b0110; 32]));
+    assert_eq!(a & b, sha256::Hash::from_byte_array([0b1000; 32]));
+    assert_eq!(a | b, sha256::Hash::from_byte_array([0b1110; 32]));
+
+    let mut c = a;
+    c ^= b;
+    assert_eq!(c, a ^ b);
+    c &= a;
+    assert_eq!(c, (a ^ b) & a);
+    c |= b;
+    assert_eq!(c, ((a ^ b) & a) | b);
+    assert_eq!(a ^ a, sha256::Hash::from_byte_array([0; 32]));
+}
+
+#[test]
+fn meets_target_be() {
+    use core::cmp::Ordering;
+
+    let mut target = [0; 32];
+    target[2] = 0x01;
+    target[31] = 0x80;
+
+    let equal = sha256::Hash::from_byte_array(target);
+    assert_eq!(equal.cmp_be(&target), Ordering::Equal);
+    assert!(equal.meets_target_be(&target));
+
+    let mut bytes = target;
+    bytes[31] -= 1;
+    let one_less = sha256::Hash::from_byte_array(bytes);
+    assert_eq!(one_less.cmp_be(&target), Ordering::Less);
+    assert!(one_less.meets_target_be(&target));
+
+    let mut bytes = target;
+    bytes[31] += 1;
+    let one_more = sha256::Hash::from_byte_array(bytes);
+    assert_eq!(one_more.cmp_be(&target), Ordering::Greater);
+    assert!(!one_more.meets_target_be(&target));
+
+    // More significant bytes dominate.
+    let mut bytes = [0xff; 32];
+    bytes[..3].copy_from_slice(&[0, 0, 0]);
+    assert!(sha256::Hash::from_byte_array(bytes).meets_target_be(&target));
+    let mut bytes = [0; 32];
+    bytes[2] = 0x02;
+    assert!(!sha256::Hash::from_byte_array(bytes).meets_target_be(&target));
+}
+
+#[test]
+fn from_engine_ref() {
+    let mut engine = sha256::Hash::engine();
+    engine.input(&[1; 100]);
+    let before = engine.to_snapshot();
+
+    assert_eq!(sha256::Hash::from_engine_ref(&engine), sha256::Hash::hash(&[1; 100]));
+    assert_eq!(engine.to_snapshot(), before);
+
+    engine.input(&[2; 100]);
+    let mut data = [1; 200];
+    data[100..].copy_from_slice(&[2; 100]);
+    assert_eq!(sha256::Hash::from_engine(engine), sha256::Hash::hash(&data));
+}
+
+#[test]
+fn all_zeros_all_ones() {
+    const ZEROS: sha256::Hash = sha256::Hash::all_zeros();
+    const ONES: sha256::Hash = sha256::Hash::all_ones();
+
+    assert_eq!(ZEROS.to_byte_array(), [0; 32]);
+    assert_eq!(ONES.to_byte_array(), [0xff; 32]);
+    assert_eq!(ZEROS ^ ONES, ONES);
+    assert!(ONES.meets_target_be(&[0xff; 32]));
+}
+
+#[test]
+fn engine_buffered_bytes() {
+    let mut engine = sha256::Hash::engine();
+    assert!(engine.buffered_bytes().is_empty());
+
+    engine.input(&[1; 70]);
+    assert_eq!(engine.buffered_bytes(), &[1; 6]);
+    assert!(!engine.can_extract_midstate());
+
+    // Stale bytes from the previous block must not be exposed.
+    engine.input(&[2; 60]);
+    assert_eq!(engine.buffered_bytes(), &[2; 2]);
+
+    engine.input(&[3; 62]);
+    assert!(engine.buffered_bytes().is_empty());
+    assert!(engine.can_extract_midstate());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn display_reversed() {
+    use alloc::string::ToString;
+
+    let hash = sha256::Hash::hash(b"abc");
+    let mut want = hash.to_byte_array();
+    want.reverse();
+    assert_eq!(hash.to_byte_array_reversed(), want);
+
+    let reversed = hash.display_reversed().to_string();
+    assert_eq!(reversed, "ad1500f261ff10b49c7a1796a36103b02322ae5dde404141eacf018fbf1678ba");
+
+    #[cfg(feature = "hex")]
+    assert_eq!(sha256::Hash::from_str_reversed(&reversed).unwrap(), hash);
+}
+
+#[test]
+fn engine_blocks_processed() {
+    let mut engine = sha256::Hash::engine();
+    assert_eq!((engine.blocks_processed(), engine.buffered_len()), (0, 0));
+
+    engine.input(&[0; 63]);
+    assert_eq!((engine.blocks_processed(), engine.buffered_len()), (0, 63));
+    engine.input(&[0; 1]);
+    assert_eq!((engine.blocks_processed(), engine.buffered_len()), (1, 0));
+    engine.input(&[0; 1]);
+    assert_eq!((engine.blocks_processed(), engine.buffered_len()), (1, 1));
+    engine.input(&[0; 200]);
+    assert_eq!((engine.blocks_processed(), engine.buffered_len()), (4, 9));
+    assert_eq!(engine.buffered_len(), engine.buffered_bytes().len());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+#[cfg(feature = "hex")]
+fn to_hex_string() {
+    use alloc::string::ToString;
+
+    let hash = sha256::Hash::hash(b"to_hex_string");
+    let lower = hash.to_hex_string(hex::Case::Lower);
+    assert_eq!(lower, hash.to_string());
+    assert_eq!(hash.to_hex_string(hex::Case::Upper), lower.to_uppercase());
+}
+
+#[test]
+fn from_str_canonical() {
+    use super::CanonicalHexError;
+
+    let hash = sha256::Hash::hash(b"canonical");
+    let mut hex = [0u8; 64];
+    for (i, b) in hash.as_byte_array().iter().enumerate() {
+        hex[2 * i] = b"0123456789abcdef"[usize::from(b >> 4)];
+        hex[2 * i + 1] = b"0123456789abcdef"[usize::from(b & 0x0f)];
+    }
+    let s = core::str::from_utf8(&hex).unwrap();
+    assert_eq!(sha256::Hash::from_str_canonical(s), Ok(hash));
+
+    let short = &s[..62];
+    let want = CanonicalHexError::InvalidLength { len: 62 };
+    assert_eq!(sha256::Hash::from_str_canonical(short), Err(want));
+
+    let mut bad = hex;
+    bad[5] = b'g';
+    let bad = core::str::from_utf8(&bad).unwrap();
+    let want = CanonicalHexError::InvalidChar { pos: 5, c: b'g' };
+    assert_eq!(sha256::Hash::from_str_canonical(bad), Err(want));
+
+    let pos = hex.iter().position(|c| c.is_ascii_lowercase()).unwrap();
+    let mut upper = hex;
+    upper[pos].make_ascii_uppercase();
+    let upper = core::str::from_utf8(&upper).unwrap();
+    let want = CanonicalHexError::NotLowercase { pos };
+    assert_eq!(sha256::Hash::from_str_canonical(upper), Err(want));
+}
+
+#[test]
+fn engine_input_with_count() {
+    let mut engine = sha256::Hash::engine();
+    let mut total = 0;
+    for data in [&b"abc"[..], &[7; 64], &[8; 200], &[]] {
+        let n = engine.input_with_count(data);
+        assert_eq!(n, data.len());
+        total += n;
+        assert_eq!(engine.n_bytes_hashed(), total as u64);
+    }
+}
+
+#[test]
+fn engine_write_state() {
+    let mut out = [0xff; 32];
+    let mut engine = sha256::Hash::engine();
+    for data in [&[][..], &[1; 64], &[2; 128]] {
+        engine.input(data);
+        let midstate = engine.midstate().unwrap();
+        assert_eq!(engine.write_state(&mut out), Ok(midstate.bytes_hashed()));
+        assert_eq!(&out[..], midstate.as_ref());
+    }
+
+    engine.input(&[3; 10]);
+    assert_eq!(engine.write_state(&mut out), Err(engine.midstate().unwrap_err()));
+}
+
+#[test]
+fn engine_split_midstate() {
+    let data: [u8; 300] = array::from_fn(|i| i as u8);
+    for len in [0, 1, 63, 64, 65, 200, 300] {
+        let mut engine = sha256::Hash::engine();
+        engine.input(&data[..len]);
+
+        let (midstate, residual) = engine.split_midstate();
+        assert_eq!(midstate.bytes_hashed(), (len - len % 64) as u64);
+        assert_eq!(residual, &data[len - len % 64..len]);
+
+        let mut resumed = sha256::HashEngine::from_midstate(midstate);
+        resumed.input(residual);
+        assert_eq!(resumed.to_snapshot(), engine.to_snapshot());
+
+        resumed.input(b"tail");
+        engine.input(b"tail");
+        assert_eq!(sha256::Hash::from_engine(resumed), sha256::Hash::from_engine(engine));
+    }
+}
+
+#[test]
+fn compress() {
+    // "abc" padded to a single block.
+    let mut block = [0; 64];
+    block[..3].copy_from_slice(b"abc");
+    block[3] = 0x80;
+    block[63] = 24;
+
+    let mut state = sha256::INITIAL_STATE;
+    sha256::compress(&mut state, &block);
+    let hash = sha256::Hash::hash(b"abc");
+    for (word, chunk) in state.iter().zip(hash.as_byte_array().chunks_exact(4)) {
+        assert_eq!(word.to_be_bytes(), chunk);
+    }
+}
+
+#[test]
+fn initial_state() {
+    let (bytes, len) = sha256::HashEngine::new().midstate().unwrap().to_parts();
+    assert_eq!(len, 0);
+    for (word, chunk) in sha256::INITIAL_STATE.iter().zip(bytes.chunks_exact(4)) {
+        assert_eq!(word.to_be_bytes(), chunk);
+    }
+}
+
+#[test]
+fn engine_feed() {
+    let mut engine = sha256::Hash::engine();
+    engine.feed([1u8, 2, 3]);
+    let slice: &[u8] = &[4; 70];
+    engine.feed(slice);
+    engine.feed("str");
+    engine.feed(sha256::Hash::hash(b"hash"));
+
+    let mut want = sha256::Hash::engine();
+    want.input(&[1, 2, 3]);
+    want.input(&[4; 70]);
+    want.input(b"str");
+    want.input(sha256::Hash::hash(b"hash").as_byte_array());
+    assert_eq!(sha256::Hash::from_engine(engine), sha256::Hash::from_engine(want));
+}
+
+#[test]
+fn engine_chain() {
+    let hash = sha256::HashEngine::new().chain(b"abc").chain(&[7; 100]).finalize();
+    let mut data = [7; 103];
+    data[..3].copy_from_slice(b"abc");
+    assert_eq!(hash, sha256::Hash::hash(&data));
+}
+
+#[test]
+fn bounded_engine() {
+    let data = [7; 100];
+
+    // Exactly at the limit.
+    let mut engine = sha256::BoundedHashEngine::new(100);
+    engine.try_input(&data[..40]).unwrap();
+    engine.try_input(&data[40..]).unwrap();
+    assert_eq!(engine.remaining(), 0);
+    assert!(engine.try_input(&[]).is_ok());
+    assert_eq!(engine.finalize(), sha256::Hash::hash(&data));
+
+    // One under the limit.
+    let mut engine = sha256::BoundedHashEngine::new(101);
+    engine.input(&data);
+    assert_eq!(engine.remaining(), 1);
+    assert_eq!(engine.finalize(), sha256::Hash::hash(&data));
+
+    // One over the limit, the rejected input is not absorbed.
+    let mut engine = sha256::BoundedHashEngine::new(99);
+    engine.try_input(&data[..50]).unwrap();
+    assert!(engine.try_input(&data[50..]).is_err());
+    assert_eq!(engine.n_bytes_hashed(), 50);
+    assert_eq!(engine.finalize(), sha256::Hash::hash(&data[..50]));
+}
+
+#[test]
+#[should_panic]
+fn bounded_engine_input_over_limit() {
+    let mut engine = sha256::BoundedHashEngine::new(99);
+    engine.input(&[7; 100]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn engine_io_write() {
+    use std::io::Write as _;
+
+    let data: [u8; 200] = array::from_fn(|i| i as u8);
+
+    let mut engine = sha256::Hash::engine();
+    let copied = std::io::copy(&mut &data[..], &mut engine).expect("engine writes don't error");
+    assert_eq!(copied, 200);
+    assert_eq!(engine.n_bytes_hashed(), 200);
+    assert_eq!(sha256::Hash::from_engine(engine), sha256::Hash::hash(&data));
+
+    let mut engine = sha256::Hash::engine();
+    assert_eq!(engine.write(&data[..64]).unwrap(), 64);
+    engine.flush().unwrap();
+    assert!(engine.can_extract_midstate());
+    engine.write_all(&data[64..]).unwrap();
+    assert_eq!(sha256::Hash::from_engine(engine), sha256::Hash::hash(&data));
+}
+
+#[test]
+fn engine_fmt_write() {
+    use core::fmt::Write as _;
+
+    let name = "abc";
+    let mut engine = sha256::Hash::engine();
+    write!(engine, "{}-{}-{:x}", 42, name, 255u8).expect("engine writes don't error");
+    assert_eq!(engine.n_bytes_hashed(), 9);
+    assert_eq!(sha256::Hash::from_engine(engine), sha256::Hash::hash(b"42-abc-ff"));
+}
+
+#[test]
+fn engine_snapshot_roundtrip() {
+    let data: [u8; 256] = array::from_fn(|i| i as u8);
+    let want = sha256::Hash::hash(&data);
+
+    for split in [0, 1, 55, 63, 64, 65, 128, 200, 256] {
+        let mut engine = sha256::Hash::engine();
+        engine.input(&data[..split]);
+        let snapshot = engine.to_snapshot();
+
+        let mut restored = sha256::HashEngine::from_snapshot(&snapshot).expect("valid snapshot");
+        assert_eq!(restored.n_bytes_hashed(), split as u64);
+        assert_eq!(restored.to_snapshot(), snapshot);
+        restored.input(&data[split..]);
+        assert_eq!(sha256::Hash::from_engine(restored), want, "mismatch for split {}", split);
+    }
+}
+
+#[test]
+fn engine_snapshot_rejects_stale_buffer_bytes() {
+    let mut engine = sha256::Hash::engine();
+    engine.input(&[1; 10]);
+    let mut snapshot = engine.to_snapshot();
+    assert!(snapshot[50..].iter().all(|&b| b == 0));
+
+    snapshot[50] = 1;
+    assert!(sha256::HashEngine::from_snapshot(&snapshot).is_err());
+}
+
+#[test]
+#[cfg(feature = "digest")]
+fn digest_roundtrip() {
+    use digest::Digest;
+
+    let data: [u8; 200] = array::from_fn(|i| i as u8);
+    let want = sha256::Hash::hash(&data);
+
+    let got = <sha256::HashEngine as Digest>::digest(data);
+    assert_eq!(got.as_slice(), want.as_byte_array());
+
+    let mut engine = <sha256::HashEngine as Digest>::new();
+    Digest::update(&mut engine, &data[..100]);
+    Digest::update(&mut engine, &data[100..]);
+    assert_eq!(Digest::finalize_reset(&mut engine).as_slice(), want.as_byte_array());
+    assert_eq!(engine.n_bytes_hashed(), 0);
+
+    Digest::update(&mut engine, data);
+    assert_eq!(Digest::finalize(engine).as_slice(), want.as_byte_array());
+}
+
+#[test]
+#[cfg(feature = "zeroize")]
+fn zeroize() {
+    use zeroize::Zeroize;
+
+    let mut engine = sha256::Hash::engine();
+    engine.input(&[0xab; 100]);
+    engine.zeroize();
+    assert_eq!(engine.buffer, [0; BLOCK_SIZE]);
+    assert_eq!(engine.h, [0; 8]);
+    assert_eq!(engine.bytes_hashed, 0);
+
+    let mut midstate = Midstate::new([0xab; 32], 64);
+    midstate.zeroize();
+    assert_eq!(midstate.to_parts(), ([0; 32], 0));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn hash_many() {
+    use alloc::vec::Vec;
+
+    let data: [u8; 256] = array::from_fn(|i| i as u8);
+
+    // Uniform batches of every length up to a few blocks, plus a partial trailing batch.
+    for len in 0..=200 {
+        let inputs: Vec<&[u8]> = (0..19).map(|i| &data[i..i + len]).collect();
+        let want: Vec<_> = inputs.iter().map(|input| sha256::Hash::hash(input)).collect();
+        assert_eq!(sha256::hash_many(&inputs), want, "mismatch for length {}", len);
+    }
+
+    // Batches of mixed lengths.
+    let inputs: Vec<&[u8]> = (0..20).map(|i| &data[..i * 7]).collect();
+    let want: Vec<_> = inputs.iter().map(|input| sha256::Hash::hash(input)).collect();
+    assert_eq!(sha256::hash_many(&inputs), want);
+
+    assert!(sha256::hash_many(&[]).is_empty());
+}
+
+#[test]
+#[cfg(all(feature = "std", target_arch = "x86_64", not(hashes_fuzz)))]
+fn hash_8_avx2() {
+    // `hash_many` doesn't use this path on CPUs with the SHA extensions, so test it directly.
+    if !crypto::x86_avx2_available() {
+        return;
+    }
+
+    let data: [u8; 256] = array::from_fn(|i| i as u8);
+    for len in 0..=200 {
+        let inputs: [&[u8]; 8] = array::from_fn(|i| &data[i..i + len]);
+        // SAFETY: We checked above that the CPU supports AVX2.
+        let digests = unsafe { crypto::hash_8_avx2(&inputs) };
+        for (input, digest) in inputs.iter().zip(&digests) {
+            assert_eq!(digest, sha256::Hash::hash(input).as_byte_array(), "length {}", len);
+        }
+    }
+}
+
 #[test]
 fn hash_unoptimized() {
     let bytes: [u8; 256] = array::from_fn(|i| i as u8);
@@ -189,6 +686,316 @@ fn hash_unoptimized() {
 #[test]
 fn const_midstate() { assert_eq!(Midstate::hash_tag(b"TapLeaf"), TAP_LEAF_MIDSTATE,) }
 
+#[test]
+fn midstate_absorb_block() {
+    let blocks: [[u8; 64]; 3] = [[1; 64], [2; 64], array::from_fn(|i| i as u8)];
+
+    let mut engine = sha256::Hash::engine();
+    let mut midstate = engine.midstate().unwrap();
+    for block in &blocks {
+        midstate.absorb_block(block);
+        engine.input(block);
+        assert_eq!(midstate, engine.midstate().unwrap());
+    }
+    assert_eq!(midstate.as_parts().1, 192);
+
+    let mut restored = sha256::HashEngine::from_midstate(midstate);
+    restored.input(b"tail");
+    engine.input(b"tail");
+    assert_eq!(sha256::Hash::from_engine(restored), sha256::Hash::from_engine(engine));
+}
+
+#[test]
+#[cfg(feature = "test-utils")]
+fn hash_deterministic_stub() {
+    let stub = sha256::Hash::hash_deterministic_stub;
+
+    assert_eq!(stub(b"abc"), stub(b"abc"));
+    assert_ne!(stub(b"abc"), stub(b"abd"));
+    assert_ne!(stub(b"abc"), sha256::Hash::hash(b"abc"));
+    // Inputs that XOR to zero must not produce the all-zeros hash.
+    assert_ne!(stub(&[]), sha256::Hash::all_zeros());
+    assert_ne!(stub(&[0; 32]), stub(&[0; 64]));
+    assert_ne!(stub(&[7; 64]), sha256::Hash::all_zeros());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn hash_vectored() {
+    use std::io::IoSlice;
+
+    let data: [u8; 300] = array::from_fn(|i| i as u8);
+    let slices = [
+        IoSlice::new(&data[..10]),
+        IoSlice::new(&[]),
+        IoSlice::new(&data[10..100]),
+        IoSlice::new(&data[100..]),
+    ];
+    assert_eq!(sha256::Hash::hash_vectored(&slices), sha256::Hash::hash(&data));
+    assert_eq!(sha256::Hash::hash_vectored(&[]), sha256::Hash::hash(&[]));
+}
+
+#[test]
+fn xor_fold() {
+    let a = sha256::Hash::hash(b"a");
+    let b = sha256::Hash::hash(b"b");
+    let c = sha256::Hash::hash(b"c");
+
+    assert_eq!(sha256::Hash::xor_fold(&[]), sha256::Hash::all_zeros());
+    assert_eq!(sha256::Hash::xor_fold(&[a]), a);
+    assert_eq!(sha256::Hash::xor_fold(&[a, b, c]), a ^ b ^ c);
+    assert_eq!(sha256::Hash::xor_fold(&[a, b, c]), sha256::Hash::xor_fold(&[c, a, b]));
+    assert_eq!(sha256::Hash::xor_fold(&[a, b, a]), b);
+}
+
+#[test]
+fn truncate() {
+    let hash = sha256::Hash::hash(b"short id");
+    let bytes = hash.as_byte_array();
+
+    assert_eq!(hash.truncate::<0>(), [0; 0]);
+    assert_eq!(hash.truncate::<4>(), bytes[..4]);
+    assert_eq!(hash.truncate::<6>(), bytes[..6]);
+    assert_eq!(hash.truncate::<32>(), *bytes);
+    assert_eq!(hash.truncate_to_u64(), u64::from_le_bytes(hash.truncate()));
+}
+
+#[test]
+#[should_panic]
+fn truncate_too_long() { sha256::Hash::hash(b"short id").truncate::<33>(); }
+
+#[test]
+fn hash_pair() {
+    let left = sha256::Hash::hash(b"left");
+    let right = sha256::Hash::hash(b"right");
+
+    let mut concat = [0; 64];
+    concat[..32].copy_from_slice(left.as_byte_array());
+    concat[32..].copy_from_slice(right.as_byte_array());
+    assert_eq!(sha256::Hash::hash_pair(&left, &right), sha256::Hash::hash(&concat));
+}
+
+#[test]
+fn hash_chunked() {
+    let bytes: [u8; 1000] = array::from_fn(|i| i as u8);
+    let want = sha256::Hash::hash(&bytes);
+    for chunk in [1, 3, 63, 64, 65, 128, 999, 1000, 4096] {
+        assert_eq!(sha256::hash_chunked(&bytes, chunk), want, "chunk size {}", chunk);
+    }
+    assert_eq!(sha256::hash_chunked(&[], 64), sha256::Hash::hash(&[]));
+}
+
+#[test]
+#[should_panic]
+fn hash_chunked_zero() { sha256::hash_chunked(b"abc", 0); }
+
+#[test]
+#[cfg(feature = "bytes")]
+fn hash_buf() {
+    use bytes::Buf as _;
+
+    let data: [u8; 200] = core::array::from_fn(|i| i as u8);
+    for split in [0, 1, 63, 64, 65, 200] {
+        let buf = (&data[..split]).chain(&data[split..]);
+        assert_eq!(sha256::hash_buf(buf), sha256::Hash::hash(&data), "split at {}", split);
+    }
+}
+
+#[test]
+fn hash_framed() {
+    let prefix = b"prefix";
+    let payload = [7; 300];
+
+    let mut engine = sha256::HashEngine::new();
+    engine.input(prefix);
+    engine.input(&300u32.to_le_bytes());
+    engine.input(&payload);
+    let want = sha256::Hash::from_engine(engine);
+
+    assert_eq!(sha256::hash_framed(prefix, &payload), Ok(want));
+}
+
+#[test]
+fn hash_to_midstate() {
+    let tag = sha256::Hash::hash(b"TapLeaf");
+    let mut prefix = [0; 64];
+    prefix[..32].copy_from_slice(tag.as_byte_array());
+    prefix[32..].copy_from_slice(tag.as_byte_array());
+    assert_eq!(sha256::hash_to_midstate(&prefix), Ok(TAP_LEAF_MIDSTATE));
+
+    assert!(sha256::hash_to_midstate(&prefix[..63]).is_err());
+    assert_eq!(sha256::hash_to_midstate(&[]), sha256::HashEngine::new().midstate());
+}
+
+#[test]
+#[rustfmt::skip]
+fn block_midstates() {
+    // Same test vector as `midstate`.
+    let mut data = [0; 150];
+    data[..32].copy_from_slice(&[
+        0x9d, 0xd0, 0x1b, 0x56, 0xb1, 0x56, 0x45, 0x14,
+        0x3e, 0xad, 0x15, 0x8d, 0xec, 0x19, 0xf8, 0xce,
+        0xa9, 0x0b, 0xd0, 0xa9, 0xb2, 0xf8, 0x1d, 0x21,
+        0xff, 0xa3, 0xa4, 0xc6, 0x44, 0x81, 0xd4, 0x1c,
+    ]);
+    let want = sha256::Midstate::new([
+        0x0b, 0xcf, 0xe0, 0xe5, 0x4e, 0x6c, 0xc7, 0xd3,
+        0x4f, 0x4f, 0x7c, 0x1d, 0xf0, 0xb0, 0xf5, 0x03,
+        0xf2, 0xf7, 0x12, 0x91, 0x2a, 0x06, 0x05, 0xb4,
+        0x14, 0xed, 0x33, 0x7f, 0x7f, 0x03, 0x2e, 0x03,
+    ], 64);
+    assert_eq!(sha256::block_midstates(&data).next(), Some(want));
+
+    assert_eq!(sha256::block_midstates(&data[..63]).count(), 0);
+    assert_eq!(sha256::block_midstates(&data).count(), 2);
+    let last = sha256::block_midstates(&data[..128]).last();
+    assert_eq!(last, Some(sha256::hash_to_midstate(&data[..128]).unwrap()));
+}
+
+#[test]
+fn hash_tagged() {
+    // TapLeaf hash of the script `OP_TRUE` with leaf version 0xc0.
+    #[rustfmt::skip]
+    let want = [
+        0xa8, 0x5b, 0x21, 0x07, 0xf7, 0x91, 0xb2, 0x6a,
+        0x84, 0xe7, 0x58, 0x6c, 0x28, 0xce, 0xc7, 0xcb,
+        0x61, 0x20, 0x2e, 0xd3, 0xd0, 0x19, 0x44, 0xd8,
+        0x32, 0x50, 0x0f, 0x36, 0x37, 0x82, 0xd6, 0x75,
+    ];
+    let msg = [0xc0, 0x01, 0x51];
+    assert_eq!(sha256::hash_tagged(&TAP_LEAF_MIDSTATE, &msg).to_byte_array(), want);
+
+    let mut engine = sha256::Hash::engine();
+    let tag = sha256::Hash::hash(b"TapLeaf");
+    engine.input(tag.as_byte_array());
+    engine.input(tag.as_byte_array());
+    engine.input(&msg);
+    assert_eq!(sha256::hash_tagged(&TAP_LEAF_MIDSTATE, &msg), sha256::Hash::from_engine(engine));
+}
+
+#[test]
+fn hash_tag_runtime() {
+    assert_eq!(Midstate::hash_tag_runtime(b"TapLeaf"), TAP_LEAF_MIDSTATE);
+    for tag in [&b""[..], b"BIP0340/challenge", &[0xab; 64], &[0xcd; 200]] {
+        assert_eq!(Midstate::hash_tag_runtime(tag), Midstate::hash_tag(tag));
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn hash_hasher_map() {
+    use std::collections::HashMap;
+
+    use super::BuildHashHasher;
+
+    let mut map = HashMap::with_hasher(BuildHashHasher::default());
+    for i in 0..1000u32 {
+        map.insert(sha256::Hash::hash(&i.to_le_bytes()), i);
+    }
+    assert_eq!(map.len(), 1000);
+    for i in 0..1000u32 {
+        assert_eq!(map.get(&sha256::Hash::hash(&i.to_le_bytes())), Some(&i));
+    }
+    assert_eq!(map.get(&sha256::Hash::hash(b"absent")), None);
+}
+
+#[test]
+fn hash_hasher_uses_prefix() {
+    use core::hash::{BuildHasher as _, Hash as _, Hasher as _};
+
+    use super::BuildHashHasher;
+
+    let hash = sha256::Hash::hash(b"key");
+    let mut hasher = BuildHashHasher::default().build_hasher();
+    hash.hash(&mut hasher);
+    let want = u64::from_le_bytes(hash.as_byte_array()[..8].try_into().unwrap());
+    assert_eq!(hasher.finish(), want);
+}
+
+#[test]
+fn midstate_const_eq() {
+    const _: () = assert!(TAP_LEAF_MIDSTATE.const_eq(&Midstate::hash_tag(b"TapLeaf")));
+
+    let (bytes, len) = TAP_LEAF_MIDSTATE.to_parts();
+    let mut other_bytes = bytes;
+    other_bytes[31] ^= 1;
+    let midstates = [
+        TAP_LEAF_MIDSTATE,
+        Midstate::new(bytes, len + 64),
+        Midstate::new(other_bytes, len),
+        Midstate::default(),
+    ];
+    for a in &midstates {
+        for b in &midstates {
+            assert_eq!(a.const_eq(b), a == b);
+        }
+    }
+}
+
+#[test]
+fn midstate_accessors() {
+    const LEN: u64 = TAP_LEAF_MIDSTATE.bytes_hashed();
+    assert_eq!(LEN, 64);
+    assert_eq!(TAP_LEAF_MIDSTATE.state(), TAP_LEAF_MIDSTATE.as_parts().0);
+}
+
+#[test]
+fn midstate_is_valid_bytes_hashed() {
+    const _: () = assert!(Midstate::is_valid_bytes_hashed(128), "not a multiple of 64");
+    assert!(Midstate::is_valid_bytes_hashed(0));
+    assert!(Midstate::is_valid_bytes_hashed(64));
+    assert!(!Midstate::is_valid_bytes_hashed(1));
+    assert!(!Midstate::is_valid_bytes_hashed(65));
+}
+
+#[test]
+fn midstate_try_new() {
+    let (bytes, len) = TAP_LEAF_MIDSTATE.to_parts();
+    assert_eq!(Midstate::try_new(bytes, len), Ok(TAP_LEAF_MIDSTATE));
+    assert!(Midstate::try_new(bytes, 0).is_ok());
+    assert!(Midstate::try_new(bytes, 63).is_err());
+    assert!(Midstate::try_new(bytes, 65).is_err());
+}
+
+#[test]
+fn midstate_into_parts() {
+    let parts: ([u8; 32], u64) = TAP_LEAF_MIDSTATE.into();
+    assert_eq!(parts, TAP_LEAF_MIDSTATE.to_parts());
+
+    let bytes: [u8; 32] = TAP_LEAF_MIDSTATE.into();
+    assert_eq!(&bytes, TAP_LEAF_MIDSTATE.as_parts().0);
+}
+
+#[test]
+fn midstate_compute() {
+    const PREFIX: [u8; 128] = [0xab; 128];
+    const MIDSTATE: Midstate = Midstate::compute(&PREFIX);
+    assert_eq!(MIDSTATE, sha256::hash_to_midstate(&PREFIX).unwrap());
+    assert_eq!(Midstate::compute(&[]), sha256::hash_to_midstate(&[]).unwrap());
+}
+
+#[test]
+#[should_panic]
+fn midstate_compute_unaligned() { let _ = Midstate::compute(b"my-protocol-v1"); }
+
+#[test]
+fn midstate_finalize() {
+    let data: [u8; 128] = array::from_fn(|i| i as u8);
+    for len in [0, 64, 128] {
+        let midstate = sha256::hash_to_midstate(&data[..len]).unwrap();
+        assert_eq!(midstate.finalize(), sha256::Hash::hash(&data[..len]));
+        let engine = sha256::HashEngine::from_midstate(midstate);
+        assert_eq!(midstate.finalize(), sha256::Hash::from_engine(engine));
+    }
+}
+
+#[test]
+fn midstate_matches_tag() {
+    assert!(TAP_LEAF_MIDSTATE.matches_tag(b"TapLeaf"));
+    assert!(!TAP_LEAF_MIDSTATE.matches_tag(b"TapBranch"));
+    assert!(!sha256::Hash::engine().midstate().unwrap().matches_tag(b""));
+}
+
 #[test]
 #[cfg(feature = "alloc")]
 fn regression_midstate_debug_format() {
@@ -201,6 +1008,7 @@ fn regression_midstate_debug_format() {
 
 #[test]
 #[cfg(feature = "serde")]
+#[allow(deprecated)] // Because of `from_slice`.
 fn sha256_serde() {
     use serde_test::{assert_tokens, Configure, Token};
 
@@ -220,6 +1028,42 @@ fn sha256_serde() {
     );
 }
 
+#[test]
+#[cfg(feature = "serde")]
+fn midstate_serde() {
+    use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Token};
+
+    let midstate = Midstate::new([0xab; 32], 128);
+    assert_tokens(
+        &midstate.compact(),
+        &[
+            Token::Tuple { len: 2 },
+            Token::BorrowedBytes(&[0xab; 32]),
+            Token::U64(128),
+            Token::TupleEnd,
+        ],
+    );
+    assert_tokens(
+        &midstate.readable(),
+        &[
+            Token::Tuple { len: 2 },
+            Token::Str("abababababababababababababababababababababababababababababababab"),
+            Token::U64(128),
+            Token::TupleEnd,
+        ],
+    );
+
+    assert_de_tokens_error::<serde_test::Readable<Midstate>>(
+        &[
+            Token::Tuple { len: 2 },
+            Token::Str("abababababababababababababababababababababababababababababababab"),
+            Token::U64(100),
+            Token::TupleEnd,
+        ],
+        "invalid number of bytes hashed 100 (should have been a multiple of 64)",
+    );
+}
+
 #[cfg(target_arch = "wasm32")]
 mod wasm_tests {
     use super::*;
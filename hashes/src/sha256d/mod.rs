@@ -12,13 +12,39 @@
 
 fn from_engine(e: HashEngine) -> Hash {
     let sha2 = sha256::Hash::from_engine(e.0);
-    let sha2d = sha256::Hash::hash(sha2.as_byte_array());
+    let sha2d = sha256::hash_32(sha2.as_byte_array());
 
     let mut ret = [0; 32];
     ret.copy_from_slice(sha2d.as_byte_array());
     Hash(ret)
 }
 
+impl Hash {
+    /// Hashes the concatenation of `left` and `right`, i.e. computes a Merkle tree node from its
+    /// two children.
+    pub fn hash_pair(left: &Hash, right: &Hash) -> Hash {
+        let left = sha256::Hash::from_byte_array(left.0);
+        let right = sha256::Hash::from_byte_array(right.0);
+        let sha2 = sha256::Hash::hash_pair(&left, &right);
+        Hash(sha256::hash_32(sha2.as_byte_array()).to_byte_array())
+    }
+
+    /// Produces a double hash from a SHA256 engine, i.e. the SHA256 hash of its hash.
+    ///
+    /// Equivalent to finalizing the engine and calling [`sha256::Hash::hash_again`].
+    pub fn from_engine_double(engine: sha256::HashEngine) -> Hash {
+        from_engine(HashEngine(engine))
+    }
+
+    /// Computes hash from `bytes` in `const` context.
+    ///
+    /// Warning: this function is inefficient. It should be only used in `const` context.
+    pub const fn hash_unoptimized(bytes: &[u8]) -> Self {
+        let sha2 = sha256::Hash::hash_unoptimized(bytes);
+        Hash(sha256::Hash::hash_unoptimized(sha2.as_byte_array()).to_byte_array())
+    }
+}
+
 /// Engine to compute SHA256d hash function.
 #[derive(Debug, Clone)]
 pub struct HashEngine(sha256::HashEngine);
@@ -26,12 +52,61 @@ fn from_engine(e: HashEngine) -> Hash {
 impl HashEngine {
     /// Constructs a new SHA256d hash engine.
     pub const fn new() -> Self { Self(sha256::HashEngine::new()) }
+
+    /// Constructs a new SHA256d hash engine continuing from the midstate of the inner SHA256
+    /// engine, see [`Self::midstate`].
+    ///
+    /// Please see docs on [`sha256::Midstate`] before using this function.
+    pub fn from_midstate(midstate: sha256::Midstate) -> Self {
+        Self(sha256::HashEngine::from_midstate(midstate))
+    }
+
+    /// Returns `true` if the midstate can be extracted from this engine.
+    ///
+    /// See [`sha256::HashEngine::can_extract_midstate`].
+    pub const fn can_extract_midstate(&self) -> bool { self.0.can_extract_midstate() }
+
+    /// Outputs the midstate of the inner SHA256 engine, i.e. of the first of the two hashes.
+    ///
+    /// This can be used to checkpoint a streamed double hash and resume it later with
+    /// [`Self::from_midstate`].
+    ///
+    /// Please see docs on [`sha256::Midstate`] before using this function.
+    pub fn midstate(&self) -> Result<sha256::Midstate, sha256::MidstateError> { self.0.midstate() }
 }
 
 impl Default for HashEngine {
     fn default() -> Self { Self::new() }
 }
 
+/// Double hashes 80 byte messages that share their first 64 bytes, e.g. block headers that only
+/// differ in their last fields.
+///
+/// The midstate after the constant 64 byte prefix is computed once, so each hash only needs to
+/// compress the block with the 16 byte tail plus the block of the outer hash.
+#[derive(Debug, Clone, Copy)]
+pub struct HeaderHasher {
+    /// Midstate of the inner hash after the prefix.
+    midstate: sha256::Midstate,
+}
+
+impl HeaderHasher {
+    /// Constructs a new hasher for messages starting with `prefix`.
+    pub fn new(prefix: &[u8; 64]) -> Self {
+        let midstate = sha256::hash_to_midstate(prefix).expect("prefix is a single block");
+        HeaderHasher { midstate }
+    }
+
+    /// Returns the double hash of `prefix || tail`.
+    pub fn hash_with_tail(&self, tail: &[u8; 16]) -> Hash {
+        use crate::HashEngine as _;
+
+        let mut engine = HashEngine::from_midstate(self.midstate);
+        engine.input(tail);
+        from_engine(engine)
+    }
+}
+
 impl crate::HashEngine for HashEngine {
     type Hash = Hash;
     type Bytes = [u8; 32];
@@ -45,7 +120,77 @@ fn finalize(self) -> Self::Hash { Hash::from_engine(self) }
 #[cfg(test)]
 mod tests {
     #[allow(unused_imports)] // whether this is used depends on features
-    use crate::sha256d;
+    use crate::{sha256, sha256d};
+
+    #[test]
+    fn hash_unoptimized() {
+        let bytes: [u8; 256] = core::array::from_fn(|i| i as u8);
+
+        for i in 0..=256 {
+            let bytes = &bytes[0..i];
+            assert_eq!(
+                sha256d::Hash::hash(bytes),
+                sha256d::Hash::hash_unoptimized(bytes),
+                "hashes don't match for n_bytes_hashed {}",
+                i
+            );
+        }
+
+        const HASH_ABC: sha256d::Hash = sha256d::Hash::hash_unoptimized(b"abc");
+        assert_eq!(HASH_ABC, sha256d::Hash::hash(b"abc"));
+    }
+
+    #[test]
+    fn engine_midstate_resume() {
+        use crate::HashEngine as _;
+
+        let data = [0xab; 300];
+
+        let mut engine = sha256d::HashEngine::new();
+        engine.input(&data[..100]);
+        assert!(!engine.can_extract_midstate());
+        assert!(engine.midstate().is_err());
+        engine.input(&data[100..128]);
+        let midstate = engine.midstate().unwrap();
+
+        let mut resumed = sha256d::HashEngine::from_midstate(midstate);
+        resumed.input(&data[128..]);
+        assert_eq!(sha256d::Hash::from_engine(resumed), sha256d::Hash::hash(&data));
+    }
+
+    #[test]
+    fn hash_pair() {
+        let left = sha256d::Hash::hash(b"left");
+        let right = sha256d::Hash::hash(b"right");
+
+        let mut concat = [0; 64];
+        concat[..32].copy_from_slice(left.as_byte_array());
+        concat[32..].copy_from_slice(right.as_byte_array());
+        assert_eq!(sha256d::Hash::hash_pair(&left, &right), sha256d::Hash::hash(&concat));
+    }
+
+    #[test]
+    fn from_engine_double() {
+        use crate::HashEngine as _;
+
+        let mut engine = sha256::HashEngine::new();
+        engine.input(&[0xab; 100]);
+        let want = sha256::Hash::from_engine(engine.clone()).hash_again();
+        assert_eq!(sha256d::Hash::from_engine_double(engine), want);
+    }
+
+    #[test]
+    fn header_hasher() {
+        let mut header: [u8; 80] = core::array::from_fn(|i| i as u8);
+        let prefix: [u8; 64] = header[..64].try_into().unwrap();
+        let hasher = sha256d::HeaderHasher::new(&prefix);
+
+        for nonce in [0u32, 1, 0xdead_beef] {
+            header[76..].copy_from_slice(&nonce.to_le_bytes());
+            let tail: [u8; 16] = header[64..].try_into().unwrap();
+            assert_eq!(hasher.hash_with_tail(&tail), sha256d::Hash::hash(&header));
+        }
+    }
 
     #[test]
     #[cfg(feature = "alloc")]
@@ -170,4 +315,27 @@ pub fn sha256d_64k(bh: &mut Bencher) {
         });
         bh.bytes = bytes.len() as u64;
     }
+
+    #[bench]
+    pub fn sha256d_80(bh: &mut Bencher) {
+        let bytes = [1u8; 80];
+        bh.iter(|| sha256d::Hash::hash(&bytes));
+        bh.bytes = bytes.len() as u64;
+    }
+
+    #[bench]
+    pub fn sha256d_80_header_hasher(bh: &mut Bencher) {
+        let hasher = sha256d::HeaderHasher::new(&[1u8; 64]);
+        let tail = [1u8; 16];
+        bh.iter(|| hasher.hash_with_tail(&tail));
+        bh.bytes = 80;
+    }
+
+    #[bench]
+    pub fn sha256d_64_finalize(bh: &mut Bencher) {
+        // A Merkle tree node, where the outer hash is a large part of the total cost.
+        let bytes = [1u8; 64];
+        bh.iter(|| sha256d::Hash::hash(&bytes));
+        bh.bytes = bytes.len() as u64;
+    }
 }
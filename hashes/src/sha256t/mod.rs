@@ -37,6 +37,14 @@ pub fn hash_byte_chunks<B, I, T>(byte_slices: I) -> Hash<T>
     engine.finalize()
 }
 
+/// Constructs a SHA256 engine tagged with a `tag` only known at runtime.
+///
+/// The engine is primed with `SHA256(tag) || SHA256(tag)`, as in BIP-340, so it is in the same
+/// state as one started from [`Midstate::hash_tag`] but computed using the optimized hashing code.
+pub fn engine_from_tag(tag: &[u8]) -> sha256::HashEngine {
+    sha256::HashEngine::from_midstate(sha256::Midstate::hash_tag_runtime(tag))
+}
+
 /// Trait representing a tag that can be used as a context for SHA256t hashes.
 pub trait Tag {
     /// The [`Midstate`] after pre-tagging the hash engine.
@@ -239,6 +247,14 @@ impl sha256t::Tag for TestHashTag {
     #[cfg(feature = "hex")]
     pub type TestHash = sha256t::Hash<TestHashTag>;
 
+    #[test]
+    fn engine_from_tag() {
+        for tag in [&b""[..], b"TapLeaf", b"BIP0340/challenge", &[0xab; 100]] {
+            let engine = sha256t::engine_from_tag(tag);
+            assert_eq!(engine.midstate().unwrap(), sha256::Midstate::hash_tag(tag));
+        }
+    }
+
     #[test]
     #[cfg(feature = "alloc")]
     #[cfg(feature = "hex")]
@@ -7,7 +7,8 @@
 #![cfg(feature = "hex")]
 
 use bitcoin_hashes::{
-    hash160, ripemd160, sha1, sha256, sha256d, sha256t, sha384, sha512, sha512_256, siphash24,
+    hash160, ripemd160, sha1, sha224, sha256, sha256d, sha256t, sha384, sha512, sha512_256,
+    siphash24,
     HashEngine as _, HmacEngine,
 };
 
@@ -31,6 +32,7 @@ fn $test_name() {
     regression_hash160, hash160, "a17909f6d5373b0085c4180ba207126e5040f74d";
     regression_ripemd160, ripemd160, "e6801701c77a1cd85662335258c7869631b4a9a8";
     regression_sha1, sha1, "e1e81eeabadafa3d5d41cc3f405385426b0f47fd";
+    regression_sha224, sha224, "2fd20a113afd37fc56e36582154ff58b8536ef846edcf06be22be860";
     regression_sha256, sha256, "d291c6c5a07fa1d9315cdae090ebe14169fbe0a219cd55a48d0d2104eab6ec51";
     regression_sha256d, sha256d, "93a743b022290bde3233a619b21aaebe06c5cf5cc959464c41be35711e37731b";
     regression_sha384, sha384, "f545bd83d297978d47a7f26b858a54188499dfb4d7d570a6a2362c765031d57a29d7e002df5e34d184e70b65a4f47153";
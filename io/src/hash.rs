@@ -9,7 +9,8 @@
 
 use hashes::hmac::HmacEngine;
 use hashes::{
-    hash160, ripemd160, sha1, sha256, sha256d, sha256t, sha384, sha512, sha512_256, siphash24,
+    hash160, ripemd160, sha1, sha224, sha256, sha256d, sha256t, sha384, sha512, sha512_256,
+    siphash24,
     HashEngine as _,
 };
 
@@ -59,6 +60,15 @@ fn flush(&mut self) -> crate::Result<()> {
     |_us| { Ok(()) }
 );
 
+impl_write!(
+    sha224::HashEngine,
+    |us: &mut sha224::HashEngine, buf| {
+        hashes::HashEngine::input(us, buf);
+        Ok(buf.len())
+    },
+    |_us| { Ok(()) }
+);
+
 impl_write!(
     sha256::HashEngine,
     |us: &mut sha256::HashEngine, buf| {
@@ -190,6 +200,13 @@ fn $mod() {
         "e4b66838f9f7b6f91e5be32a02ae78094df402e7",
     );
 
+    write_test!(
+        sha224,
+        "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f",
+        "4729c84e59381c0cc83d7171caf635a2ccffe4adbfd6db9e0e32999e",
+        "a72e39979b827f94653d6f7da918cc3ffc3ae6ba271fa50f706b5bdf",
+    );
+
     write_test!(
         sha256,
         "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
@@ -306,6 +323,7 @@ fn $test_name() {
         hash_from_reader_hash160, hash160, "a17909f6d5373b0085c4180ba207126e5040f74d";
         hash_from_reader_ripemd160, ripemd160, "e6801701c77a1cd85662335258c7869631b4a9a8";
         hash_from_reader_sha1, sha1, "e1e81eeabadafa3d5d41cc3f405385426b0f47fd";
+        hash_from_reader_sha224, sha224, "2fd20a113afd37fc56e36582154ff58b8536ef846edcf06be22be860";
         hash_from_reader_sha256, sha256, "d291c6c5a07fa1d9315cdae090ebe14169fbe0a219cd55a48d0d2104eab6ec51";
         hash_from_reader_sha256d, sha256d, "93a743b022290bde3233a619b21aaebe06c5cf5cc959464c41be35711e37731b";
         hash_from_reader_sha384, sha384, "f545bd83d297978d47a7f26b858a54188499dfb4d7d570a6a2362c765031d57a29d7e002df5e34d184e70b65a4f47153";